@@ -1,14 +1,19 @@
+use crate::stdlib::prelude::*;
 use crate::{
     hint_processor::hint_processor_definition::HintProcessor,
     types::{
         builtin_name::BuiltinName, layout::CairoLayoutParams, layout_name::LayoutName,
-        program::Program,
+        program::Program, relocatable::MaybeRelocatable,
     },
     vm::{
         errors::{
             cairo_run_errors::CairoRunError, runner_errors::RunnerError, vm_exception::VmException,
         },
-        runners::{cairo_pie::CairoPie, cairo_runner::CairoRunner},
+        runners::{
+            builtin_runner::BuiltinRunner,
+            cairo_pie::CairoPie,
+            cairo_runner::{CairoRunner, RunResources},
+        },
         security::verify_secure_runner,
     },
 };
@@ -23,6 +28,7 @@ use crate::types::exec_scope::ExecutionScopes;
 use arbitrary::{self, Arbitrary};
 
 #[cfg_attr(feature = "test_utils", derive(Arbitrary))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct CairoRunConfig<'a> {
     #[cfg_attr(feature = "test_utils", arbitrary(value = "main"))]
     pub entrypoint: &'a str,
@@ -33,9 +39,24 @@ pub struct CairoRunConfig<'a> {
     /// It is ignored otherwise.
     pub dynamic_layout_params: Option<CairoLayoutParams>,
     pub proof_mode: bool,
+    /// Runs one more step after reaching the end of the program in proof mode. Disable it if the
+    /// proof setup must not execute instructions past the end of the program.
+    pub proof_mode_extra_step: bool,
     pub secure_run: Option<bool>,
     pub disable_trace_padding: bool,
     pub allow_missing_builtins: Option<bool>,
+    /// When running a Cairo PIE, resets the hint processor's `RunResources` to the PIE's step count
+    /// instead of returning an error if they don't match.
+    pub override_pie_n_steps: bool,
+    /// Makes the ec_op builtin yield the point at infinity sentinel (0, 0) instead of failing when
+    /// adding two points with the same x coordinate. Ignored when `secure_run` is enabled and when
+    /// running a Cairo PIE.
+    pub ec_op_same_x_as_infinity: bool,
+    /// Maximum number of segments the segment arena builtin can allocate, if any.
+    pub segment_arena_max_segments: Option<usize>,
+    /// Records the time spent by each builtin deducing memory cells, see `CairoRunner::builtin_timings`.
+    /// Ignored unless the `profile_builtins` feature is enabled.
+    pub profile_builtins: bool,
 }
 
 impl<'a> Default for CairoRunConfig<'a> {
@@ -46,10 +67,15 @@ impl<'a> Default for CairoRunConfig<'a> {
             relocate_mem: false,
             layout: LayoutName::plain,
             proof_mode: false,
+            proof_mode_extra_step: true,
             secure_run: None,
             disable_trace_padding: false,
             allow_missing_builtins: None,
             dynamic_layout_params: None,
+            override_pie_n_steps: false,
+            ec_op_same_x_as_infinity: false,
+            segment_arena_max_segments: None,
+            profile_builtins: false,
         }
     }
 }
@@ -78,15 +104,27 @@ pub fn cairo_run_program_with_initial_scope(
     )?;
 
     cairo_runner.exec_scopes = exec_scopes;
+    cairo_runner.set_segment_arena_max_segments(cairo_run_config.segment_arena_max_segments);
+    #[cfg(feature = "profile_builtins")]
+    if cairo_run_config.profile_builtins {
+        cairo_runner.vm.enable_builtin_profiling();
+    }
 
     let end = cairo_runner.initialize(allow_missing_builtins)?;
+    if cairo_run_config.ec_op_same_x_as_infinity && !secure_run {
+        for builtin in cairo_runner.vm.builtin_runners.iter_mut() {
+            if let BuiltinRunner::EcOp(ec_op) = builtin {
+                ec_op.set_same_x_as_infinity(true);
+            }
+        }
+    }
     // check step calculation
 
     cairo_runner
         .run_until_pc(end, hint_processor)
         .map_err(|err| VmException::from_vm_error(&cairo_runner, err))?;
 
-    if cairo_run_config.proof_mode {
+    if cairo_run_config.proof_mode && cairo_run_config.proof_mode_extra_step {
         cairo_runner.run_for_steps(1, hint_processor)?;
     }
     cairo_runner.end_run(
@@ -135,14 +173,23 @@ pub fn cairo_run(
 /// Note: Cairo PIEs cannot be ran in proof_mode
 /// WARNING: As the RunResources are part of the HintProcessor trait, the caller should make sure that
 /// the number of steps in the `RunResources` matches that of the `ExecutionResources` in the `CairoPie`.
-/// An error will be returned if this doesn't hold.
+/// An error will be returned if this doesn't hold, unless `override_pie_n_steps` is set in the `CairoRunConfig`,
+/// in which case the hint processor's `RunResources` are reset to the PIE's step count.
+/// Use `RunResources::for_pie` to build matching resources, i.e.:
+/// `BuiltinHintProcessor::new(Default::default(), RunResources::for_pie(&pie))`
 pub fn cairo_run_pie(
     pie: &CairoPie,
     cairo_run_config: &CairoRunConfig,
     hint_processor: &mut dyn HintProcessor,
 ) -> Result<CairoRunner, CairoRunError> {
     if cairo_run_config.proof_mode {
-        return Err(RunnerError::CairoPieProofMode.into());
+        return Err(RunnerError::CairoPieProofMode {
+            layout: cairo_run_config.layout,
+        }
+        .into());
+    }
+    if cairo_run_config.override_pie_n_steps {
+        hint_processor.set_run_resources(RunResources::for_pie(pie));
     }
     if !hint_processor
         .get_n_steps()
@@ -212,6 +259,31 @@ pub fn cairo_run_pie(
     Ok(cairo_runner)
 }
 
+/// Runs both programs with the given config and returns whether they are execution equivalent,
+/// that is, whether they produce the same relocated trace and memory.
+/// Each program runs with a fresh hint processor built by `new_hint_processor`, so that state left
+/// by the first run (such as spent `RunResources`) can't affect the second one.
+/// Tracing and memory relocation are always enabled for these runs, regardless of the config.
+/// Both programs need to be runnable under the config, as any error in either run is returned.
+pub fn programs_equivalent<H: HintProcessor>(
+    a: &Program,
+    b: &Program,
+    cairo_run_config: &CairoRunConfig,
+    mut new_hint_processor: impl FnMut() -> H,
+) -> Result<bool, CairoRunError> {
+    let cairo_run_config = CairoRunConfig {
+        trace_enabled: true,
+        relocate_mem: true,
+        dynamic_layout_params: cairo_run_config.dynamic_layout_params.clone(),
+        ..*cairo_run_config
+    };
+    let runner_a = cairo_run_program(a, &cairo_run_config, &mut new_hint_processor())?;
+    let runner_b = cairo_run_program(b, &cairo_run_config, &mut new_hint_processor())?;
+
+    Ok(runner_a.relocated_trace == runner_b.relocated_trace
+        && runner_a.relocated_memory == runner_b.relocated_memory)
+}
+
 #[cfg(feature = "test_utils")]
 pub fn cairo_run_fuzzed_program(
     program: Program,
@@ -265,6 +337,30 @@ pub fn cairo_run_fuzzed_program(
 #[error("Failed to encode trace at position {0}, serialize error: {1}")]
 pub struct EncodeTraceError(usize, bincode::error::EncodeError);
 
+/// Byte order used when encoding the trace and memory binary representations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big,
+}
+
+impl Endianness {
+    fn encode_u64(self, value: u64) -> [u8; 8] {
+        match self {
+            Endianness::Little => value.to_le_bytes(),
+            Endianness::Big => value.to_be_bytes(),
+        }
+    }
+
+    fn encode_felt(self, value: &Felt252) -> [u8; 32] {
+        match self {
+            Endianness::Little => value.to_bytes_le(),
+            Endianness::Big => value.to_bytes_be(),
+        }
+    }
+}
+
 /// Writes the trace binary representation.
 ///
 /// Bincode encodes to little endian by default and each trace entry is composed of
@@ -272,35 +368,130 @@ pub struct EncodeTraceError(usize, bincode::error::EncodeError);
 pub fn write_encoded_trace(
     relocated_trace: &[crate::vm::trace::trace_entry::RelocatedTraceEntry],
     dest: &mut impl Writer,
+) -> Result<(), EncodeTraceError> {
+    write_encoded_trace_with_endianness(relocated_trace, dest, Endianness::Little)
+}
+
+/// Writes the trace binary representation with the given byte order.
+///
+/// The field order (ap, fp, pc) is the same as in [write_encoded_trace].
+pub fn write_encoded_trace_with_endianness(
+    relocated_trace: &[crate::vm::trace::trace_entry::RelocatedTraceEntry],
+    dest: &mut impl Writer,
+    endianness: Endianness,
 ) -> Result<(), EncodeTraceError> {
     for (i, entry) in relocated_trace.iter().enumerate() {
-        dest.write(&((entry.ap as u64).to_le_bytes()))
+        dest.write(&endianness.encode_u64(entry.ap as u64))
             .map_err(|e| EncodeTraceError(i, e))?;
-        dest.write(&((entry.fp as u64).to_le_bytes()))
+        dest.write(&endianness.encode_u64(entry.fp as u64))
             .map_err(|e| EncodeTraceError(i, e))?;
-        dest.write(&((entry.pc as u64).to_le_bytes()))
+        dest.write(&endianness.encode_u64(entry.pc as u64))
             .map_err(|e| EncodeTraceError(i, e))?;
     }
 
     Ok(())
 }
 
+/// Returns the trace binary representation, as written by [write_encoded_trace].
+pub fn encode_trace(
+    relocated_trace: &[crate::vm::trace::trace_entry::RelocatedTraceEntry],
+) -> Vec<u8> {
+    let mut writer = VecWriter(Vec::with_capacity(relocated_trace.len() * 3 * 8));
+    // Writing to a VecWriter can't fail
+    let _ = write_encoded_trace(relocated_trace, &mut writer);
+    writer.0
+}
+
+/// Writes a hex dump of the trace binary representation (as encoded by [write_encoded_trace]),
+/// for quick inspection on a terminal.
+///
+/// Each line holds up to 16 bytes, prefixed by the offset of its first byte, i.e.:
+/// `00000010: 05 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00`
+pub fn hexdump_trace(
+    relocated_trace: &[crate::vm::trace::trace_entry::RelocatedTraceEntry],
+    dest: &mut impl core::fmt::Write,
+) -> core::fmt::Result {
+    let mut writer = HexdumpWriter { dest, offset: 0 };
+    write_encoded_trace(relocated_trace, &mut writer).map_err(|_| core::fmt::Error)?;
+    // Terminate the last line if it wasn't filled
+    if writer.offset % 16 != 0 {
+        writeln!(writer.dest)?;
+    }
+
+    Ok(())
+}
+
+/// A [Writer] collecting the encoded bytes in memory.
+struct VecWriter(Vec<u8>);
+
+impl Writer for VecWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), bincode::error::EncodeError> {
+        self.0.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// A [Writer] printing the encoded bytes as a hex dump, in lines of 16 bytes.
+struct HexdumpWriter<'a, W: core::fmt::Write> {
+    dest: &'a mut W,
+    offset: usize,
+}
+
+impl<W: core::fmt::Write> HexdumpWriter<'_, W> {
+    fn write_byte(&mut self, byte: u8) -> core::fmt::Result {
+        if self.offset % 16 == 0 {
+            write!(self.dest, "{:08x}:", self.offset)?;
+        }
+        write!(self.dest, " {byte:02x}")?;
+        self.offset += 1;
+        if self.offset % 16 == 0 {
+            writeln!(self.dest)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: core::fmt::Write> Writer for HexdumpWriter<'_, W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), bincode::error::EncodeError> {
+        for byte in bytes {
+            self.write_byte(*byte)
+                .map_err(|_| bincode::error::EncodeError::Other("failed to write the hex dump"))?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Writes a binary representation of the relocated memory.
 ///
 /// The memory pairs (address, value) are encoded and concatenated:
 /// * address -> 8-byte encoded
 /// * value -> 32-byte encoded
+///
+/// Empty cells are skipped, and the pairs are always written in ascending address order.
 pub fn write_encoded_memory(
     relocated_memory: &[Option<Felt252>],
     dest: &mut impl Writer,
+) -> Result<(), EncodeTraceError> {
+    write_encoded_memory_with_endianness(relocated_memory, dest, Endianness::Little)
+}
+
+/// Writes a binary representation of the relocated memory with the given byte order, for both
+/// addresses and values.
+///
+/// The layout is the same as in [write_encoded_memory].
+pub fn write_encoded_memory_with_endianness(
+    relocated_memory: &[Option<Felt252>],
+    dest: &mut impl Writer,
+    endianness: Endianness,
 ) -> Result<(), EncodeTraceError> {
     for (i, memory_cell) in relocated_memory.iter().enumerate() {
         match memory_cell {
             None => continue,
             Some(unwrapped_memory_cell) => {
-                dest.write(&(i as u64).to_le_bytes())
+                dest.write(&endianness.encode_u64(i as u64))
                     .map_err(|e| EncodeTraceError(i, e))?;
-                dest.write(&unwrapped_memory_cell.to_bytes_le())
+                dest.write(&endianness.encode_felt(unwrapped_memory_cell))
                     .map_err(|e| EncodeTraceError(i, e))?;
             }
         }
@@ -309,11 +500,66 @@ pub fn write_encoded_memory(
     Ok(())
 }
 
+/// Returns the relocated memory binary representation, as written by [write_encoded_memory].
+pub fn encode_memory(relocated_memory: &[Option<Felt252>]) -> Vec<u8> {
+    let used_cells = relocated_memory.iter().flatten().count();
+    let mut writer = VecWriter(Vec::with_capacity(used_cells * (8 + 32)));
+    // Writing to a VecWriter can't fail
+    let _ = write_encoded_memory(relocated_memory, &mut writer);
+    writer.0
+}
+
+/// Determines how [write_encoded_maybe_memory] handles memory cells holding a relocatable value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelocatableEncoding {
+    /// Relocatable cells are left out, as if they were empty.
+    Skip,
+    /// Relocatable cells are encoded in a tagged 32-byte form, so that they can't be mistaken for
+    /// a felt: the segment index (8 bytes, as an i64) and the offset (8 bytes), followed by zeros
+    /// and a last byte set to `0xff`, which is never the case for an encoded felt.
+    Tagged,
+}
+
+/// Writes a binary representation of a (possibly not yet relocated) memory.
+///
+/// Integer cells are encoded in the same way as [write_encoded_memory] does, while relocatable
+/// cells are skipped or encoded according to `relocatable_encoding`.
+pub fn write_encoded_maybe_memory(
+    memory: &[Option<MaybeRelocatable>],
+    dest: &mut impl Writer,
+    relocatable_encoding: RelocatableEncoding,
+) -> Result<(), EncodeTraceError> {
+    for (i, memory_cell) in memory.iter().enumerate() {
+        let encoded_value = match memory_cell {
+            None => continue,
+            Some(MaybeRelocatable::Int(value)) => value.to_bytes_le(),
+            Some(MaybeRelocatable::RelocatableValue(_))
+                if relocatable_encoding == RelocatableEncoding::Skip =>
+            {
+                continue
+            }
+            Some(MaybeRelocatable::RelocatableValue(relocatable)) => {
+                let mut encoded = [0; 32];
+                encoded[..8].copy_from_slice(&(relocatable.segment_index as i64).to_le_bytes());
+                encoded[8..16].copy_from_slice(&(relocatable.offset as u64).to_le_bytes());
+                encoded[31] = 0xff;
+                encoded
+            }
+        };
+        dest.write(&(i as u64).to_le_bytes())
+            .map_err(|e| EncodeTraceError(i, e))?;
+        dest.write(&encoded_value)
+            .map_err(|e| EncodeTraceError(i, e))?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::stdlib::prelude::*;
-    use crate::vm::runners::cairo_runner::RunResources;
+    use crate::vm::runners::cairo_runner::{ResourceTracker, RunResources};
+    use crate::vm::trace::trace_entry::RelocatedTraceEntry;
     use crate::Felt252;
     use crate::{
         hint_processor::{
@@ -322,6 +568,7 @@ mod tests {
         },
         utils::test_utils::*,
     };
+    use assert_matches::assert_matches;
     use bincode::enc::write::SliceWriter;
 
     use rstest::rstest;
@@ -386,6 +633,24 @@ mod tests {
         assert!(cairo_run(no_main_program, &cairo_run_config, &mut hint_processor,).is_err());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_with_missing_entrypoint() {
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let program = include_bytes!("../../cairo_programs/manually_compiled/valid_program_a.json");
+        let cairo_run_config = CairoRunConfig {
+            entrypoint: "not_main",
+            ..Default::default()
+        };
+        let error = cairo_run(program, &cairo_run_config, &mut hint_processor)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Entrypoint not_main not found. Available entrypoints: [main]"
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn cairo_run_with_invalid_memory() {
@@ -411,6 +676,24 @@ mod tests {
         assert_eq!(&output_buffer, "0\n");
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_output_values_program() {
+        let program_content = include_bytes!("../../cairo_programs/bitwise_output.json");
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut runner = run_test_program(program_content, &mut hint_processor)
+            .expect("Couldn't initialize cairo runner");
+
+        let mut output_buffer = String::new();
+        runner.vm.write_output(&mut output_buffer).unwrap();
+        let printed: Vec<Felt252> = output_buffer
+            .lines()
+            .map(|line| Felt252::from_dec_str(line).unwrap())
+            .collect();
+        assert_eq!(runner.vm.get_output_values(), Ok(printed));
+        assert_eq!(runner.vm.get_output_values(), Ok(vec![Felt252::ZERO]));
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn write_binary_trace_file() {
@@ -457,6 +740,242 @@ mod tests {
         assert_eq!(*expected_encoded_memory, buffer);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_trace_matches_trace_file() {
+        let program_content = include_bytes!("../../cairo_programs/struct.json");
+        let expected_encoded_trace =
+            include_bytes!("../../cairo_programs/trace_memory/cairo_trace_struct");
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = run_test_program(program_content, &mut hint_processor).unwrap();
+        assert!(cairo_runner.relocate(false).is_ok());
+
+        let encoded_trace = encode_trace(cairo_runner.relocated_trace.as_ref().unwrap());
+        assert_eq!(encoded_trace, expected_encoded_trace);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_memory_matches_memory_file() {
+        let program_content = include_bytes!("../../cairo_programs/struct.json");
+        let expected_encoded_memory =
+            include_bytes!("../../cairo_programs/trace_memory/cairo_memory_struct");
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = run_test_program(program_content, &mut hint_processor).unwrap();
+        assert!(cairo_runner.relocate(true).is_ok());
+
+        let encoded_memory = encode_memory(&cairo_runner.relocated_memory);
+        assert_eq!(encoded_memory, expected_encoded_memory);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_matches_writer_output() {
+        let trace = vec![
+            RelocatedTraceEntry {
+                pc: 5,
+                ap: 18,
+                fp: 18,
+            },
+            RelocatedTraceEntry {
+                pc: 6,
+                ap: 19,
+                fp: 18,
+            },
+        ];
+        let mut buffer = [0; 48];
+        write_encoded_trace(&trace, &mut SliceWriter::new(&mut buffer)).unwrap();
+        assert_eq!(encode_trace(&trace), buffer);
+
+        let memory = vec![None, Some(Felt252::from(7)), None, Some(Felt252::from(-1))];
+        let mut buffer = [0; 80];
+        write_encoded_memory(&memory, &mut SliceWriter::new(&mut buffer)).unwrap();
+        assert_eq!(encode_memory(&memory), buffer);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_trace_and_memory_round_trip_both_endiannesses() {
+        let trace = vec![
+            RelocatedTraceEntry {
+                pc: 5,
+                ap: 18,
+                fp: 18,
+            },
+            RelocatedTraceEntry {
+                pc: 6,
+                ap: 19,
+                fp: 18,
+            },
+        ];
+        let memory = vec![None, Some(Felt252::from(7)), None, Some(Felt252::from(-1))];
+
+        for endianness in [Endianness::Little, Endianness::Big] {
+            let decode_u64 = |bytes: &[u8]| {
+                let bytes = bytes.try_into().unwrap();
+                match endianness {
+                    Endianness::Little => u64::from_le_bytes(bytes),
+                    Endianness::Big => u64::from_be_bytes(bytes),
+                }
+            };
+
+            let mut buffer = [0; 48];
+            write_encoded_trace_with_endianness(
+                &trace,
+                &mut SliceWriter::new(&mut buffer),
+                endianness,
+            )
+            .unwrap();
+            let decoded_trace: Vec<RelocatedTraceEntry> = buffer
+                .chunks(24)
+                .map(|entry| RelocatedTraceEntry {
+                    ap: decode_u64(&entry[..8]) as usize,
+                    fp: decode_u64(&entry[8..16]) as usize,
+                    pc: decode_u64(&entry[16..]) as usize,
+                })
+                .collect();
+            assert_eq!(decoded_trace, trace);
+
+            let mut buffer = [0; 80];
+            write_encoded_memory_with_endianness(
+                &memory,
+                &mut SliceWriter::new(&mut buffer),
+                endianness,
+            )
+            .unwrap();
+            let mut decoded_memory = vec![None; memory.len()];
+            for pair in buffer.chunks(40) {
+                let value: &[u8; 32] = pair[8..].try_into().unwrap();
+                decoded_memory[decode_u64(&pair[..8]) as usize] = Some(match endianness {
+                    Endianness::Little => Felt252::from_bytes_le(value),
+                    Endianness::Big => Felt252::from_bytes_be(value),
+                });
+            }
+            assert_eq!(decoded_memory, memory);
+        }
+
+        // The default encoding is little endian, and big endian differs from it
+        let mut little = [0; 48];
+        let mut big = [0; 48];
+        write_encoded_trace(&trace, &mut SliceWriter::new(&mut little)).unwrap();
+        write_encoded_trace_with_endianness(
+            &trace,
+            &mut SliceWriter::new(&mut big),
+            Endianness::Big,
+        )
+        .unwrap();
+        assert_eq!(encode_trace(&trace), little);
+        assert_ne!(little, big);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn hexdump_small_trace() {
+        let trace = vec![
+            RelocatedTraceEntry {
+                pc: 5,
+                ap: 18,
+                fp: 18,
+            },
+            RelocatedTraceEntry {
+                pc: 6,
+                ap: 19,
+                fp: 18,
+            },
+        ];
+        let mut dump = String::new();
+        hexdump_trace(&trace, &mut dump).unwrap();
+
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "00000000: 12 00 00 00 00 00 00 00 12 00 00 00 00 00 00 00"
+        );
+        assert_eq!(
+            lines[2],
+            "00000020: 12 00 00 00 00 00 00 00 06 00 00 00 00 00 00 00"
+        );
+
+        // A partially filled last line is terminated too
+        let mut dump = String::new();
+        hexdump_trace(&trace[..1], &mut dump).unwrap();
+        assert_eq!(
+            dump,
+            "00000000: 12 00 00 00 00 00 00 00 12 00 00 00 00 00 00 00\n\
+             00000010: 05 00 00 00 00 00 00 00\n"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn write_encoded_sparse_memory_sorted_addresses() {
+        let mut relocated_memory = vec![None; 100];
+        for (addr, value) in [(90, 1), (3, 2), (47, 3), (1, 4), (64, 5)] {
+            relocated_memory[addr] = Some(Felt252::from(value));
+        }
+        let mut buffer = [0; 200];
+        let mut buff_writer = SliceWriter::new(&mut buffer);
+        write_encoded_memory(&relocated_memory, &mut buff_writer).unwrap();
+        assert_eq!(buff_writer.bytes_written(), 200);
+
+        let addresses: Vec<u64> = buffer
+            .chunks(40)
+            .map(|pair| u64::from_le_bytes(pair[..8].try_into().unwrap()))
+            .collect();
+        assert_eq!(addresses, vec![1, 3, 47, 64, 90]);
+        assert!(addresses.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn write_encoded_maybe_memory_skip_relocatables() {
+        let memory = vec![
+            Some(MaybeRelocatable::from(5)),
+            None,
+            Some(MaybeRelocatable::from((1, 2))),
+            Some(MaybeRelocatable::from(7)),
+        ];
+        let mut buffer = [0; 80];
+        let mut buff_writer = SliceWriter::new(&mut buffer);
+        write_encoded_maybe_memory(&memory, &mut buff_writer, RelocatableEncoding::Skip).unwrap();
+        assert_eq!(buff_writer.bytes_written(), 80);
+
+        let mut expected = [0; 80];
+        expected[8] = 5;
+        expected[40] = 3;
+        expected[48] = 7;
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn write_encoded_maybe_memory_tagged_relocatables() {
+        let memory = vec![
+            Some(MaybeRelocatable::from(5)),
+            None,
+            Some(MaybeRelocatable::from((1, 2))),
+            Some(MaybeRelocatable::from(7)),
+        ];
+        let mut buffer = [0; 120];
+        let mut buff_writer = SliceWriter::new(&mut buffer);
+        write_encoded_maybe_memory(&memory, &mut buff_writer, RelocatableEncoding::Tagged).unwrap();
+        assert_eq!(buff_writer.bytes_written(), 120);
+
+        let mut expected = [0; 120];
+        expected[8] = 5;
+        // Relocatable (1, 2) at address 2
+        expected[40] = 2;
+        expected[48] = 1;
+        expected[56] = 2;
+        expected[79] = 0xff;
+        expected[80] = 3;
+        expected[88] = 7;
+        assert_eq!(buffer, expected);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_with_no_trace() {
@@ -505,6 +1024,66 @@ mod tests {
         assert!(cairo_run_pie(&cairo_pie, &cairo_run_config, &mut hint_processor).is_ok());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn programs_equivalent_same_and_modified_program() {
+        let program = |value: i64| {
+            program!(
+                builtins = vec![BuiltinName::range_check],
+                data = vec_data!(
+                    (4612671182993129469_i64),
+                    (5189976364521848832_i64),
+                    (18446744073709551615_i128),
+                    (5199546496550207487_i64),
+                    (4612389712311386111_i64),
+                    (5198983563776393216_i64),
+                    (2),
+                    (2345108766317314046_i64),
+                    (5191102247248822272_i64),
+                    (5189976364521848832_i64),
+                    (value),
+                    (1226245742482522112_i64),
+                    ((
+                        "3618502788666131213697322783095070105623107215331596699973092056135872020470",
+                        10
+                    )),
+                    (2345108766317314046_i64)
+                ),
+                main = Some(8),
+            )
+        };
+        let cairo_run_config = CairoRunConfig {
+            layout: LayoutName::all_cairo,
+            ..Default::default()
+        };
+
+        assert_matches!(
+            programs_equivalent(
+                &program(7),
+                &program(7),
+                &cairo_run_config,
+                BuiltinHintProcessor::new_empty
+            ),
+            Ok(true)
+        );
+        assert_matches!(
+            programs_equivalent(
+                &program(7),
+                &program(5),
+                &cairo_run_config,
+                BuiltinHintProcessor::new_empty
+            ),
+            Ok(false)
+        );
+        // Each run gets the whole step budget, which only covers a single run
+        assert_matches!(
+            programs_equivalent(&program(7), &program(7), &cairo_run_config, || {
+                BuiltinHintProcessor::new(Default::default(), RunResources::new(12))
+            }),
+            Ok(true)
+        );
+    }
+
     #[test]
     fn cairo_run_pie_n_steps_not_set() {
         // First run program to get Cairo PIE
@@ -528,4 +1107,94 @@ mod tests {
             CairoRunError::Runner(RunnerError::PieNStepsVsRunResourcesNStepsMismatch)
         )));
     }
+
+    #[test]
+    fn cairo_run_pie_override_n_steps() {
+        // First run program to get Cairo PIE
+        let cairo_pie = {
+            let runner = cairo_run(
+                include_bytes!("../../cairo_programs/fibonacci.json"),
+                &CairoRunConfig::default(),
+                &mut BuiltinHintProcessor::new_empty(),
+            )
+            .unwrap();
+            runner.get_cairo_pie().unwrap()
+        };
+        let cairo_run_config = CairoRunConfig {
+            override_pie_n_steps: true,
+            ..Default::default()
+        };
+        // Run Cairo PIE with a mismatched step count
+        let mut hint_processor = BuiltinHintProcessor::new(
+            Default::default(),
+            RunResources::new(cairo_pie.execution_resources.n_steps + 1),
+        );
+        assert!(cairo_run_pie(&cairo_pie, &cairo_run_config, &mut hint_processor).is_ok());
+    }
+
+    #[test]
+    fn cairo_run_pie_with_run_resources_for_pie() {
+        // First run program to get Cairo PIE
+        let cairo_pie = {
+            let runner = cairo_run(
+                include_bytes!("../../cairo_programs/fibonacci.json"),
+                &CairoRunConfig::default(),
+                &mut BuiltinHintProcessor::new_empty(),
+            )
+            .unwrap();
+            runner.get_cairo_pie().unwrap()
+        };
+        let run_resources = RunResources::for_pie(&cairo_pie);
+        assert_eq!(
+            run_resources.get_n_steps(),
+            Some(cairo_pie.execution_resources.n_steps)
+        );
+        // Run Cairo PIE
+        let mut hint_processor = BuiltinHintProcessor::new(Default::default(), run_resources);
+        assert!(cairo_run_pie(&cairo_pie, &CairoRunConfig::default(), &mut hint_processor).is_ok());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn proof_mode_extra_step() {
+        let program = proof_mode_loop_program();
+        let run = |proof_mode_extra_step| {
+            let cairo_run_config = CairoRunConfig {
+                proof_mode: true,
+                proof_mode_extra_step,
+                disable_trace_padding: true,
+                ..Default::default()
+            };
+            cairo_run_program(
+                &program,
+                &cairo_run_config,
+                &mut BuiltinHintProcessor::new_empty(),
+            )
+            .unwrap()
+        };
+        let with_extra_step = run(true);
+        let without_extra_step = run(false);
+        assert_eq!(with_extra_step.vm.current_step, 2);
+        assert_eq!(without_extra_step.vm.current_step, 1);
+        assert!(without_extra_step.vm.segments.segment_used_sizes.is_some());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_config_eq_and_hash() {
+        use crate::stdlib::collections::HashSet;
+
+        let config = CairoRunConfig::default();
+        assert!(config == CairoRunConfig::default());
+        let proof_config = CairoRunConfig {
+            proof_mode: true,
+            ..Default::default()
+        };
+        assert!(config != proof_config);
+
+        let configs: HashSet<CairoRunConfig> = [config, CairoRunConfig::default(), proof_config]
+            .into_iter()
+            .collect();
+        assert_eq!(configs.len(), 2);
+    }
 }
@@ -24,6 +24,7 @@ use super::{
 };
 use crate::Felt252;
 use crate::{
+    any_box,
     hint_processor::{
         builtin_hint_processor::secp::ec_utils::{
             ec_double_assign_new_x, ec_double_assign_new_x_v2,
@@ -107,13 +108,18 @@ use crate::{
                 verify_multiplicity_body, verify_usort,
             },
         },
-        hint_processor_definition::HintReference,
+        hint_processor_definition::{get_ids_data, HintReference},
     },
     serde::deserialize_program::ApTracking,
     stdlib::{any::Any, collections::HashMap, prelude::*, rc::Rc},
     types::exec_scope::ExecutionScopes,
-    vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
+    vm::{
+        errors::{hint_errors::HintError, vm_errors::VirtualMachineError},
+        vm_core::VirtualMachine,
+    },
 };
+use lazy_static::lazy_static;
+use starknet_types_core::hash::{Poseidon, StarkHash};
 
 #[cfg(feature = "test_utils")]
 use crate::hint_processor::builtin_hint_processor::skip_next_instruction::skip_next_instruction;
@@ -130,6 +136,8 @@ pub struct HintProcessorData {
     pub code: String,
     pub ap_tracking: ApTracking,
     pub ids_data: HashMap<String, HintReference>,
+    /// Extra hint registered by the hash of its code, resolved when compiling the hint
+    pub hint_func: Option<Rc<HintFunc>>,
 }
 
 impl HintProcessorData {
@@ -138,6 +146,7 @@ impl HintProcessorData {
             code,
             ap_tracking: ApTracking::default(),
             ids_data,
+            hint_func: None,
         }
     }
 }
@@ -156,30 +165,181 @@ pub struct HintFunc(
     >,
 );
 pub struct BuiltinHintProcessor {
+    /// Hints matched by their exact code. When normalizing hint codes, hints inserted here
+    /// directly rather than through [Self::add_hint] are only matched by their exact code.
     pub extra_hints: HashMap<String, Rc<HintFunc>>,
+    extra_hints_by_hash: HashMap<Felt252, Rc<HintFunc>>,
     run_resources: RunResources,
+    normalize_hint_codes: bool,
+    /// Maps the normalized code of each extra hint to its code, only filled when normalizing
+    normalized_extra_hints: HashMap<String, String>,
+    hint_execution_counts: Option<HashMap<String, usize>>,
 }
 impl BuiltinHintProcessor {
     pub fn new_empty() -> Self {
         BuiltinHintProcessor {
             extra_hints: HashMap::new(),
+            extra_hints_by_hash: HashMap::new(),
             run_resources: RunResources::default(),
+            normalize_hint_codes: false,
+            normalized_extra_hints: HashMap::new(),
+            hint_execution_counts: None,
         }
     }
 
     pub fn new(extra_hints: HashMap<String, Rc<HintFunc>>, run_resources: RunResources) -> Self {
+        Self::new_with_hint_code_normalization(extra_hints, run_resources, false)
+    }
+
+    /// Creates a BuiltinHintProcessor which, if `normalize_hint_codes` is set, normalizes the
+    /// whitespace of each hint code at compile time so that hints reformatted by a different
+    /// compiler version still match the known hint codes.
+    /// See [normalize_hint_code] for the applied transformations.
+    pub fn new_with_hint_code_normalization(
+        extra_hints: HashMap<String, Rc<HintFunc>>,
+        run_resources: RunResources,
+        normalize_hint_codes: bool,
+    ) -> Self {
+        let normalized_extra_hints = if normalize_hint_codes {
+            extra_hints
+                .keys()
+                .map(|code| (normalize_hint_code(code), code.clone()))
+                .collect()
+        } else {
+            HashMap::new()
+        };
         BuiltinHintProcessor {
             extra_hints,
+            extra_hints_by_hash: HashMap::new(),
             run_resources,
+            normalize_hint_codes,
+            normalized_extra_hints,
+            hint_execution_counts: None,
         }
     }
 
     pub fn add_hint(&mut self, hint_code: String, hint_func: Rc<HintFunc>) {
+        if self.normalize_hint_codes {
+            self.normalized_extra_hints
+                .insert(normalize_hint_code(&hint_code), hint_code.clone());
+        }
         self.extra_hints.insert(hint_code, hint_func);
     }
+
+    /// Registers a hint by the hash of its code, as computed by [hint_code_hash].
+    /// Hints missing from `extra_hints` are looked up by hash when they are compiled.
+    pub fn add_hint_by_hash(&mut self, hint_code_hash: Felt252, hint_func: Rc<HintFunc>) {
+        self.extra_hints_by_hash.insert(hint_code_hash, hint_func);
+    }
+
+    /// Makes this hint processor count how many times each hint code is executed.
+    /// Counting is disabled by default.
+    pub fn enable_hint_execution_counts(&mut self) {
+        self.hint_execution_counts.get_or_insert_with(HashMap::new);
+    }
+
+    /// Returns how many times each hint code was executed by this hint processor, adding up the
+    /// executions of a hint code at every pc it appears in,
+    /// or `None` if counting wasn't enabled with [Self::enable_hint_execution_counts]
+    pub fn hint_execution_counts(&self) -> Option<&HashMap<String, usize>> {
+        self.hint_execution_counts.as_ref()
+    }
+}
+
+/// Returns the Poseidon hash of a hint code, used as key by [BuiltinHintProcessor::add_hint_by_hash].
+/// The code is normalized with [normalize_hint_code] and its bytes are packed into felts of 31
+/// bytes each before hashing, so that hint codes differing only in their whitespace share a hash.
+pub fn hint_code_hash(hint_code: &str) -> Felt252 {
+    let felts: Vec<Felt252> = normalize_hint_code(hint_code)
+        .as_bytes()
+        .chunks(31)
+        .map(Felt252::from_bytes_be_slice)
+        .collect();
+    Poseidon::hash_array(&felts)
+}
+
+/// Normalizes the whitespace of a hint code:
+/// - Line endings are converted to `\n`
+/// - Trailing whitespace is stripped from every line
+/// - The indentation common to all non-empty lines is removed
+/// - Leading and trailing empty lines are dropped
+///
+/// Relative indentation and whitespace within a line are kept, as they are meaningful in python.
+pub fn normalize_hint_code(hint_code: &str) -> String {
+    let lines: Vec<&str> = hint_code.lines().map(str::trim_end).collect();
+    let indent = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or_else(|| line.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
+
+lazy_static! {
+    /// Maps each normalized builtin hint code to the first hint code it was built from
+    static ref NORMALIZED_HINT_CODES: HashMap<String, &'static str> = {
+        let mut codes = HashMap::new();
+        for code in hint_code::ALL_HINT_CODES {
+            codes.entry(normalize_hint_code(code)).or_insert(*code);
+        }
+        codes
+    };
+}
+
+impl BuiltinHintProcessor {
+    /// Returns the hint code of the extra hint or builtin hint matching `hint_code` once both are
+    /// normalized, or the normalized `hint_code` if there is none.
+    fn resolve_normalized_hint_code(&self, hint_code: &str) -> String {
+        if self.extra_hints.contains_key(hint_code) {
+            return hint_code.to_string();
+        }
+        let normalized = normalize_hint_code(hint_code);
+        if let Some(code) = self.normalized_extra_hints.get(&normalized) {
+            return code.clone();
+        }
+        match NORMALIZED_HINT_CODES.get(&normalized) {
+            Some(code) => code.to_string(),
+            None => normalized,
+        }
+    }
 }
 
 impl HintProcessorLogic for BuiltinHintProcessor {
+    fn compile_hint(
+        &self,
+        hint_code: &str,
+        ap_tracking_data: &ApTracking,
+        reference_ids: &HashMap<String, usize>,
+        references: &[HintReference],
+    ) -> Result<Box<dyn Any>, VirtualMachineError> {
+        let code = if self.normalize_hint_codes {
+            self.resolve_normalized_hint_code(hint_code)
+        } else {
+            hint_code.to_string()
+        };
+        let hint_func =
+            if self.extra_hints_by_hash.is_empty() || self.extra_hints.contains_key(&code) {
+                None
+            } else {
+                self.extra_hints_by_hash
+                    .get(&hint_code_hash(&code))
+                    .cloned()
+            };
+        Ok(any_box!(HintProcessorData {
+            code,
+            ap_tracking: ap_tracking_data.clone(),
+            ids_data: get_ids_data(reference_ids, references)?,
+            hint_func,
+        }))
+    }
+
     fn execute_hint(
         &mut self,
         vm: &mut VirtualMachine,
@@ -191,7 +351,21 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             .downcast_ref::<HintProcessorData>()
             .ok_or(HintError::WrongHintData)?;
 
-        if let Some(hint_func) = self.extra_hints.get(&hint_data.code) {
+        if let Some(hint_execution_counts) = self.hint_execution_counts.as_mut() {
+            // Only the first execution of each hint code allocates its key
+            match hint_execution_counts.get_mut(hint_data.code.as_str()) {
+                Some(count) => *count += 1,
+                None => {
+                    hint_execution_counts.insert(hint_data.code.clone(), 1);
+                }
+            }
+        }
+
+        if let Some(hint_func) = self
+            .extra_hints
+            .get(&hint_data.code)
+            .or(hint_data.hint_func.as_ref())
+        {
             return hint_func.0(
                 vm,
                 exec_scopes,
@@ -895,17 +1069,23 @@ impl ResourceTracker for BuiltinHintProcessor {
     fn run_resources(&self) -> &RunResources {
         &self.run_resources
     }
+
+    fn set_run_resources(&mut self, run_resources: RunResources) {
+        self.run_resources = run_resources;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::stdlib::any::Any;
-    use crate::types::relocatable::Relocatable;
 
     use crate::{
         any_box,
-        types::{exec_scope::ExecutionScopes, relocatable::MaybeRelocatable},
+        types::{
+            exec_scope::ExecutionScopes,
+            relocatable::{MaybeRelocatable, Relocatable},
+        },
         utils::test_utils::*,
         vm::{
             errors::{exec_scope_errors::ExecScopeError, memory_errors::MemoryError},
@@ -917,6 +1097,146 @@ mod tests {
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn hint_execution_counts_loop() {
+        let program = countdown_program(
+            Vec::new(),
+            vec![
+                hint_params(hint_code::VM_ENTER_SCOPE, HashMap::new()),
+                hint_params(hint_code::VM_EXIT_SCOPE, HashMap::new()),
+                hint_params(hint_code::VM_ENTER_SCOPE, HashMap::new()),
+            ],
+        );
+        let mut cairo_runner = cairo_runner!(program);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        assert_eq!(hint_processor.hint_execution_counts(), None);
+        hint_processor.enable_hint_execution_counts();
+        let end = cairo_runner.initialize(false).unwrap();
+        cairo_runner.run_until_pc(end, &mut hint_processor).unwrap();
+        assert_eq!(
+            hint_processor.hint_execution_counts(),
+            Some(&HashMap::from([
+                (hint_code::VM_ENTER_SCOPE.to_string(), 20),
+                (hint_code::VM_EXIT_SCOPE.to_string(), 10)
+            ]))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compile_hint_with_normalization_matches_reindented_hint() {
+        // Same hint, indented and with windows line endings
+        let hint_code = hint_code::UNSIGNED_DIV_REM
+            .lines()
+            .map(|line| format!("    {line}  "))
+            .collect::<Vec<_>>()
+            .join("\r\n");
+        assert_ne!(hint_code, hint_code::UNSIGNED_DIV_REM);
+
+        let compile = |hint_processor: &BuiltinHintProcessor| {
+            hint_processor
+                .compile_hint(&hint_code, &ApTracking::new(), &HashMap::new(), &[])
+                .unwrap()
+                .downcast::<HintProcessorData>()
+                .unwrap()
+                .code
+        };
+
+        let hint_processor = BuiltinHintProcessor::new_empty();
+        assert_eq!(compile(&hint_processor), hint_code);
+
+        let hint_processor = BuiltinHintProcessor::new_with_hint_code_normalization(
+            HashMap::new(),
+            RunResources::default(),
+            true,
+        );
+        assert_eq!(compile(&hint_processor), hint_code::UNSIGNED_DIV_REM);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compile_hint_with_normalization_matches_reindented_extra_hints() {
+        let compile = |hint_processor: &BuiltinHintProcessor, hint_code: &str| {
+            hint_processor
+                .compile_hint(hint_code, &ApTracking::new(), &HashMap::new(), &[])
+                .unwrap()
+                .downcast::<HintProcessorData>()
+                .unwrap()
+                .code
+        };
+        let hint_func = Rc::new(HintFunc(Box::new(enter_scope)));
+
+        let mut hint_processor = BuiltinHintProcessor::new_with_hint_code_normalization(
+            HashMap::from([(
+                String::from("# Custom hint a\nenter_scope()"),
+                hint_func.clone(),
+            )]),
+            RunResources::default(),
+            true,
+        );
+        hint_processor.add_hint(String::from("# Custom hint b\nenter_scope()"), hint_func);
+
+        assert_eq!(
+            compile(&hint_processor, "  # Custom hint a  \r\n  enter_scope()\n"),
+            "# Custom hint a\nenter_scope()"
+        );
+        assert_eq!(
+            compile(&hint_processor, "\n    # Custom hint b\n    enter_scope()"),
+            "# Custom hint b\nenter_scope()"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compile_hint_with_normalization_runs_hint_with_trailing_whitespace() {
+        // The builtin hint code itself has trailing whitespace
+        let hint_code = hint_code::UNSIGNED_DIV_REM_UINT768_BY_UINT384;
+        assert_ne!(normalize_hint_code(hint_code), hint_code);
+
+        let mut hint_processor = BuiltinHintProcessor::new_with_hint_code_normalization(
+            HashMap::new(),
+            RunResources::default(),
+            true,
+        );
+        let mut hint_data = hint_processor
+            .compile_hint(hint_code, &ApTracking::new(), &HashMap::new(), &[])
+            .unwrap()
+            .downcast::<HintProcessorData>()
+            .unwrap();
+        assert_eq!(hint_data.code, hint_code);
+        hint_data.ids_data = non_continuous_ids_data![
+            ("a", -17),
+            ("div", -11),
+            ("quotient", -8),
+            ("remainder", -2)
+        ];
+
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 17;
+        vm.segments = segments![
+            ((1, 0), 1),
+            ((1, 1), 2),
+            ((1, 2), 3),
+            ((1, 3), 4),
+            ((1, 4), 5),
+            ((1, 5), 6),
+            ((1, 6), 6),
+            ((1, 7), 7),
+            ((1, 8), 8)
+        ];
+        let hint_data: Box<dyn Any> = hint_data;
+        hint_processor
+            .execute_hint(
+                &mut vm,
+                &mut ExecutionScopes::new(),
+                &hint_data,
+                &HashMap::new(),
+            )
+            .unwrap();
+        check_memory![vm.segments.memory, ((1, 12), 0), ((1, 17), 3)];
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_alloc_hint_empty_memory() {
@@ -1334,4 +1654,46 @@ mod tests {
         );
         assert_eq!(exec_scopes.data.len(), 3);
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn add_hint_by_hash_executes_hint() {
+        let hint_code = "# Custom hint\nenter_scope_custom()";
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        hint_processor.add_hint_by_hash(
+            hint_code_hash(hint_code),
+            Rc::new(HintFunc(Box::new(enter_scope))),
+        );
+        let mut vm = vm!();
+        let exec_scopes = exec_scopes_ref!();
+        // The hash is computed on the normalized hint code
+        for code in [
+            hint_code,
+            "    # Custom hint\r\n    enter_scope_custom()  \n",
+        ] {
+            let hint_data = hint_processor
+                .compile_hint(code, &ApTracking::new(), &HashMap::new(), &[])
+                .unwrap();
+            assert_matches!(
+                hint_processor.execute_hint(&mut vm, exec_scopes, &hint_data, &HashMap::new()),
+                Ok(())
+            );
+        }
+        assert_eq!(exec_scopes.data.len(), 3);
+        // Resolving the hint by hash doesn't register it by code
+        assert!(hint_processor.extra_hints.is_empty());
+
+        let hint_data = hint_processor
+            .compile_hint(
+                "enter_scope_unknown()",
+                &ApTracking::new(),
+                &HashMap::new(),
+                &[],
+            )
+            .unwrap();
+        assert_matches!(
+            hint_processor.execute_hint(&mut vm, exec_scopes, &hint_data, &HashMap::new()),
+            Err(HintError::UnknownHint(_))
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use crate::math_utils::curve::STARK_BETA;
 use crate::stdlib::{borrow::Cow, boxed::Box, collections::HashMap, prelude::*};
 use crate::utils::CAIRO_PRIME;
 use crate::Felt252;
@@ -14,7 +15,7 @@ use crate::{
 use lazy_static::lazy_static;
 use num_bigint::BigUint;
 use num_bigint::ToBigInt;
-use num_traits::{Num, One, Pow, ToPrimitive, Zero};
+use num_traits::{One, Pow, ToPrimitive, Zero};
 use sha2::{Digest, Sha256};
 
 use super::hint_utils::get_ptr_from_var_name;
@@ -183,11 +184,7 @@ fn random_ec_point_seeded(seed_bytes: Vec<u8>) -> Result<(Felt252, Felt252), Hin
 }
 const ALPHA: u32 = 1;
 lazy_static! {
-    static ref BETA: BigUint = BigUint::from_str_radix(
-        "3141592653589793238462643383279502884197169399375105820974944592307816406665",
-        10
-    )
-    .unwrap();
+    static ref BETA: BigUint = STARK_BETA.to_biguint();
     static ref FELT_MAX_HALVED: BigUint = Felt252::MAX.to_biguint() / 2_u32;
 }
 
@@ -221,6 +218,7 @@ mod tests {
     use crate::hint_processor::hint_processor_definition::HintProcessorLogic;
     use crate::relocatable;
     use crate::types::relocatable::Relocatable;
+    use num_traits::Num;
     use num_traits::Zero;
 
     use crate::hint_processor::builtin_hint_processor::hint_code;
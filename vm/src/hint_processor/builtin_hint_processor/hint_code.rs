@@ -1,3 +1,18 @@
+/// Declares each hint code as a `pub const` and collects them all into `ALL_HINT_CODES`
+macro_rules! hint_codes {
+    ($($(#[$attr:meta])* pub const $name:ident: &str = $code:expr;)*) => {
+        $(
+            $(#[$attr])*
+            pub const $name: &str = $code;
+        )*
+
+        /// Every hint code above, used to match hint codes regardless of their whitespace
+        /// (see `BuiltinHintProcessor::new_with_hint_code_normalization`)
+        pub(crate) const ALL_HINT_CODES: &[&str] = &[$($(#[$attr])* $name,)*];
+    };
+}
+
+hint_codes! {
 pub const ADD_SEGMENT: &str = "memory[ap] = segments.add()";
 
 pub const VM_ENTER_SCOPE: &str = "vm_enter_scope()";
@@ -1462,3 +1477,4 @@ ids.check_account_value = res["account_value"]
 ids.check_excess_balance = res["excess_balance"]
 ids.check_margin_requirement_d = res["margin_requirement"]
 ids.check_unrealized_pnl_d = res["unrealized_pnl"]"#;
+}
@@ -301,6 +301,21 @@ mod tests {
     };
     use assert_matches::assert_matches;
 
+    #[test]
+    fn unsafe_keccak_known_digest() {
+        let mut vm = vm!();
+        // keccak256("abc")
+        vm.segments = segments![((1, 0), 3), ((1, 1), (2, 0)), ((2, 0), 0x616263)];
+        vm.set_fp(4);
+        let ids_data = ids_data!["length", "data", "high", "low"];
+        assert_matches!(run_hint!(vm, ids_data, hint_code::UNSAFE_KECCAK), Ok(()));
+        check_memory!(
+            vm.segments.memory,
+            ((1, 2), ("4e03657aea45a94fc7d47ba826c8d667", 16)),
+            ((1, 3), ("c0d1e6e33a64a036ec44f58fa12d6c45", 16))
+        );
+    }
+
     #[test]
     fn split_output_0() {
         let mut vm = vm!();
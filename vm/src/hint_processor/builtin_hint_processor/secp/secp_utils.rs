@@ -1,8 +1,9 @@
 use core::str::FromStr;
 
-use crate::stdlib::{boxed::Box, prelude::*};
+use crate::stdlib::{boxed::Box, collections::HashMap, prelude::*};
 
 use crate::vm::errors::hint_errors::HintError;
+use crate::Felt252;
 
 use lazy_static::lazy_static;
 use num_bigint::{BigInt, BigUint};
@@ -87,6 +88,33 @@ pub fn bigint3_split(integer: &num_bigint::BigUint) -> Result<[num_bigint::BigUi
     Ok(canonical_repr)
 }
 
+/// Rebuilds the curve order N = N0 + N1 * BASE + N2 * BASE**2 from the program constants.
+/// Returns `HintError::MissingConstant` if any of the limbs is missing.
+pub fn reconstruct_n(constants: &HashMap<String, Felt252>) -> Result<BigInt, HintError> {
+    reconstruct_from_limbs(constants, [N0, N1, N2])
+}
+
+/// Rebuilds the field prime P = P0 + P1 * BASE + P2 * BASE**2 from the program constants.
+/// Returns `HintError::MissingConstant` if any of the limbs is missing.
+pub fn reconstruct_p(constants: &HashMap<String, Felt252>) -> Result<BigInt, HintError> {
+    reconstruct_from_limbs(constants, [P0, P1, P2])
+}
+
+fn reconstruct_from_limbs(
+    constants: &HashMap<String, Felt252>,
+    limb_names: [&'static str; 3],
+) -> Result<BigInt, HintError> {
+    limb_names
+        .iter()
+        .rev()
+        .try_fold(BigInt::zero(), |acc, name| {
+            let limb = constants
+                .get(*name)
+                .ok_or_else(|| HintError::MissingConstant(Box::new(name)))?;
+            Ok((acc << 86_usize) + limb.to_bigint())
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,6 +127,58 @@ mod tests {
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn reconstruct_n_and_p() {
+        let constants = HashMap::from([
+            (
+                N0.to_string(),
+                Felt252::from(10428087374290690730508609_u128),
+            ),
+            (
+                N1.to_string(),
+                Felt252::from(77371252455330678278691517_u128),
+            ),
+            (
+                N2.to_string(),
+                Felt252::from(19342813113834066795298815_u128),
+            ),
+            (
+                P0.to_string(),
+                Felt252::from(77371252455336262886226991_u128),
+            ),
+            (
+                P1.to_string(),
+                Felt252::from(77371252455336267181195263_u128),
+            ),
+            (
+                P2.to_string(),
+                Felt252::from(19342813113834066795298815_u128),
+            ),
+        ]);
+        assert_matches!(reconstruct_n(&constants), Ok(n) if n == *N);
+        assert_matches!(reconstruct_p(&constants), Ok(p) if p == *SECP_P);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn reconstruct_n_missing_constant() {
+        let constants = HashMap::from([
+            (
+                N0.to_string(),
+                Felt252::from(10428087374290690730508609_u128),
+            ),
+            (
+                N2.to_string(),
+                Felt252::from(19342813113834066795298815_u128),
+            ),
+        ]);
+        assert_matches!(
+            reconstruct_n(&constants),
+            Err(HintError::MissingConstant(name)) if *name == N1
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn secp_split() {
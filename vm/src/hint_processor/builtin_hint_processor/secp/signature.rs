@@ -5,9 +5,9 @@ use crate::{
         builtin_hint_processor::{hint_utils::get_integer_from_var_name, secp::secp_utils::BETA},
         hint_processor_definition::HintReference,
     },
-    math_utils::{div_mod, safe_div_bigint},
+    math_utils::{div_mod, safe_div_bigint, sqrt_prime_power},
     serde::deserialize_program::ApTracking,
-    stdlib::{collections::HashMap, ops::Shr, prelude::*},
+    stdlib::{collections::HashMap, prelude::*},
     types::exec_scope::ExecutionScopes,
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
@@ -118,8 +118,14 @@ pub fn get_point_from_x(
         .pack86()
         .mod_floor(&SECP_P);
     let y_cube_int = (x_cube_int + beta).mod_floor(&SECP_P);
-    // Divide by 4
-    let mut y = y_cube_int.modpow(&(&*SECP_P + 1_u32).shr(2_u32), &SECP_P);
+    // Fails if y_cube_int is not a quadratic residue, as there is no point with this x
+    let Some(y) = sqrt_prime_power(
+        &y_cube_int.to_biguint().unwrap_or_default(),
+        &SECP_P.to_biguint().unwrap_or_default(),
+    ) else {
+        return Err(HintError::SecpPointNotOnCurve(Box::new(y_cube_int)));
+    };
+    let mut y = BigInt::from(y);
 
     let v = get_integer_from_var_name("v", vm, ids_data, ap_tracking)?.to_bigint();
     if v.is_even() != y.is_even() {
@@ -162,6 +168,7 @@ pub fn pack_modn_div_modn(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hint_processor::builtin_hint_processor::secp::secp_utils::SECP256R1_N;
     use crate::stdlib::string::ToString;
     use crate::types::errors::math_errors::MathError;
 
@@ -305,6 +312,85 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_point_from_x_not_on_curve() {
+        let hint_code = hint_code::GET_POINT_FROM_X;
+        let mut vm = vm!();
+        // x = 0 -> y^2 = 7, which has no square root modulo SECP_P
+        vm.segments = segments![((1, 0), 1), ((1, 1), 0), ((1, 2), 0), ((1, 3), 0)];
+        vm.run_context.fp = 1;
+        let ids_data = non_continuous_ids_data![("v", -1), ("x_cube", 0)];
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code,
+                exec_scopes_ref!(),
+                &[(BETA, Felt252::from(7)),]
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v))
+                    .collect()
+            ),
+            Err(HintError::SecpPointNotOnCurve(bx)) if *bx == bigint!(7)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn div_mod_n_packed_external_n_secp256r1() {
+        let mut vm = vm!();
+        let mut exec_scopes = ExecutionScopes::new();
+        vm.segments = segments![
+            ((1, 0), 100),
+            ((1, 1), 99),
+            ((1, 2), 98),
+            ((1, 3), 10),
+            ((1, 4), 9),
+            ((1, 5), 8)
+        ];
+        vm.run_context.fp = 3;
+        let ids_data = non_continuous_ids_data![("a", -3), ("b", 0)];
+        // secp256r1 programs import N before dividing
+        assert_matches!(
+            run_hint!(
+                vm,
+                HashMap::new(),
+                hint_code::IMPORT_SECP256R1_N,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::DIV_MOD_N_PACKED_DIVMOD_EXTERNAL_N,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        assert_eq!(exec_scopes.get::<BigInt>("N").unwrap(), *SECP256R1_N);
+        check_scope!(
+            &exec_scopes,
+            [(
+                "value",
+                bigint_str!(
+                    "100060256736217824884104738485427713956954574853273218757168376648800677791486"
+                )
+            )]
+        );
+        assert_matches!(
+            run_hint!(
+                vm,
+                HashMap::new(),
+                hint_code::DIV_MOD_N_SAFE_DIV,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn pack_modn_div_modn_ok() {
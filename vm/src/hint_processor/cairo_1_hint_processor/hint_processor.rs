@@ -1255,4 +1255,8 @@ impl ResourceTracker for Cairo1HintProcessor {
     fn run_resources(&self) -> &RunResources {
         &self.run_resources
     }
+
+    fn set_run_resources(&mut self, run_resources: RunResources) {
+        self.run_resources = run_resources;
+    }
 }
@@ -1,13 +1,17 @@
-use crate::stdlib::boxed::Box;
+use crate::stdlib::{borrow::Cow, boxed::Box, prelude::*};
 
 use crate::{
+    math_utils::signed_felt,
     serde::deserialize_program::{ApTracking, OffsetValue},
     types::{
         errors::math_errors::MathError,
         instruction::Register,
         relocatable::{MaybeRelocatable, Relocatable},
     },
-    vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
+    vm::{
+        errors::{hint_errors::HintError, vm_errors::VirtualMachineError},
+        vm_core::VirtualMachine,
+    },
 };
 
 use super::hint_processor_definition::HintReference;
@@ -34,12 +38,43 @@ pub fn get_integer_from_reference(
     hint_reference: &HintReference,
     ap_tracking: &ApTracking,
 ) -> Result<Felt252, HintError> {
+    get_integer_from_reference_checked(vm, hint_reference, ap_tracking, false)
+}
+
+///Returns the Integer value stored in the given ids variable
+///If `strict` is set, also fails with `HintError::TypeMismatch` if the reference has a
+///`cairo_type` other than `felt`, which catches pointers and structs being read as felts
+pub fn get_integer_from_reference_checked(
+    vm: &VirtualMachine,
+    hint_reference: &HintReference,
+    ap_tracking: &ApTracking,
+    strict: bool,
+) -> Result<Felt252, HintError> {
+    if strict {
+        if let Some(cairo_type) = hint_reference
+            .cairo_type
+            .as_deref()
+            .filter(|cairo_type| *cairo_type != "felt")
+        {
+            return Err(HintError::TypeMismatch(cairo_type.into()));
+        }
+    }
     get_maybe_relocatable_from_reference(vm, hint_reference, ap_tracking)
         .ok_or(HintError::UnknownIdentifierInternal)?
         .get_int()
         .ok_or(HintError::WrongIdentifierTypeInternal)
 }
 
+///Returns the `n` integers stored in consecutive cells starting at `base`.
+///Fails with the address of the first cell that is either missing or not an integer.
+pub fn get_integer_range(
+    vm: &VirtualMachine,
+    base: Relocatable,
+    n: usize,
+) -> Result<Vec<Cow<Felt252>>, VirtualMachineError> {
+    Ok(vm.get_integer_range(base, n)?)
+}
+
 ///Returns the Relocatable value stored in the given ids variable
 pub fn get_ptr_from_reference(
     vm: &VirtualMachine,
@@ -52,6 +87,26 @@ pub fn get_ptr_from_reference(
         .ok_or(HintError::WrongIdentifierTypeInternal)
 }
 
+///Returns the Relocatable value stored in the given ids variable, along with the address of the variable.
+///If the reference is not dereferenced, the value of the variable is its address, so both are the same.
+pub fn get_ptr_and_addr_from_reference(
+    vm: &VirtualMachine,
+    hint_reference: &HintReference,
+    ap_tracking: &ApTracking,
+) -> Result<(Relocatable, Relocatable), HintError> {
+    let addr = compute_addr_from_reference(hint_reference, vm, ap_tracking)
+        .ok_or(HintError::UnknownIdentifierInternal)?;
+    if !hint_reference.outer_dereference {
+        return Ok((addr, addr));
+    }
+    let ptr = vm
+        .get_maybe(&addr)
+        .ok_or(HintError::UnknownIdentifierInternal)?
+        .get_relocatable()
+        .ok_or(HintError::WrongIdentifierTypeInternal)?;
+    Ok((ptr, addr))
+}
+
 ///Returns the value given by a reference as [MaybeRelocatable]
 pub fn get_maybe_relocatable_from_reference(
     vm: &VirtualMachine,
@@ -124,6 +179,14 @@ pub fn felt_to_usize(felt: &Felt252) -> Result<usize, MathError> {
         .ok_or_else(|| MathError::Felt252ToUsizeConversion(Box::new(*felt)))
 }
 
+///Tries to convert a Felt252 value to isize, using its signed representation
+///(values above PRIME / 2 are interpreted as negative)
+pub fn felt_to_isize(felt: &Felt252) -> Result<isize, VirtualMachineError> {
+    signed_felt(*felt)
+        .to_isize()
+        .ok_or_else(|| VirtualMachineError::BigintToIsizeFail(Box::new(*felt)))
+}
+
 ///Tries to convert a Felt252 value to u32
 pub fn felt_to_u32(felt: &Felt252) -> Result<u32, MathError> {
     felt.to_u32()
@@ -164,7 +227,11 @@ fn get_offset_value(
 mod tests {
     use super::*;
 
-    use crate::{relocatable, utils::test_utils::*, vm::vm_memory::memory::Memory};
+    use crate::{
+        relocatable,
+        utils::test_utils::*,
+        vm::{errors::memory_errors::MemoryError, vm_memory::memory::Memory},
+    };
     use assert_matches::assert_matches;
 
     #[cfg(target_arch = "wasm32")]
@@ -186,6 +253,102 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_integer_from_reference_checked_type() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 7)];
+        let mut hint_ref = HintReference::new_simple(0);
+        hint_ref.cairo_type = Some("felt*".to_string());
+
+        // Only strict mode checks the reference type
+        assert_eq!(
+            get_integer_from_reference_checked(&vm, &hint_ref, &ApTracking::new(), false).unwrap(),
+            Felt252::from(7)
+        );
+        assert_matches!(
+            get_integer_from_reference_checked(&vm, &hint_ref, &ApTracking::new(), true),
+            Err(HintError::TypeMismatch(cairo_type)) if cairo_type.as_ref() == "felt*"
+        );
+
+        hint_ref.cairo_type = Some("felt".to_string());
+        assert_eq!(
+            get_integer_from_reference_checked(&vm, &hint_ref, &ApTracking::new(), true).unwrap(),
+            Felt252::from(7)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_integer_from_program_cast_reference() {
+        let program_json = br#"
+            {
+                "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+                "attributes": [],
+                "debug_info": null,
+                "builtins": [],
+                "data": [],
+                "identifiers": {},
+                "hints": {},
+                "reference_manager": {
+                    "references": [
+                        {
+                            "ap_tracking_data": {
+                                "group": 0,
+                                "offset": 0
+                            },
+                            "pc": 0,
+                            "value": "[cast([fp + (-4)] + 1, felt*)]"
+                        }
+                    ]
+                }
+            }"#;
+        let program = crate::types::program::Program::from_bytes(program_json, None).unwrap();
+        let hint_ref = &program.shared_program_data.reference_manager[0];
+
+        let mut vm = vm!();
+        vm.set_fp(4);
+        vm.segments = segments![((1, 0), (2, 0)), ((2, 1), 42)];
+
+        assert_eq!(
+            get_integer_from_reference(&vm, hint_ref, &ApTracking::new())
+                .expect("Unexpected get integer fail"),
+            Felt252::from(42)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_integer_range_ok() {
+        let mut vm = vm!();
+        vm.segments = segments![
+            ((1, 0), 1),
+            ((1, 1), 2),
+            ((1, 2), 3),
+            ((1, 3), 4),
+            ((1, 4), 5)
+        ];
+
+        let range = get_integer_range(&vm, relocatable!(1, 0), 5).unwrap();
+        assert_eq!(
+            range.iter().map(|x| **x).collect::<Vec<_>>(),
+            (1..=5).map(Felt252::from).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_integer_range_gap() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 1), ((1, 1), 2), ((1, 3), 4), ((1, 4), 5)];
+
+        assert_matches!(
+            get_integer_range(&vm, relocatable!(1, 0), 5),
+            Err(VirtualMachineError::Memory(MemoryError::UnknownMemoryCell(bx)))
+                if *bx == relocatable!(1, 2)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_offset_value_reference_valid() {
@@ -251,6 +414,40 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_ptr_and_addr_from_reference_with_dereference() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (3, 0))];
+
+        assert_matches!(
+            get_ptr_and_addr_from_reference(
+                &vm,
+                &HintReference::new(0, 0, false, true),
+                &ApTracking::new()
+            ),
+            Ok((ptr, addr)) if ptr == relocatable!(3, 0) && addr == relocatable!(1, 0)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_ptr_and_addr_from_reference_without_dereference() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (4, 0))];
+        let mut hint_ref = HintReference::new(0, 0, true, false);
+        hint_ref.offset2 = OffsetValue::Value(2);
+
+        assert_matches!(
+            get_ptr_and_addr_from_reference(&vm, &hint_ref, &ApTracking::new()),
+            Ok((ptr, addr)) if ptr == relocatable!(4, 2) && addr == relocatable!(4, 2)
+        );
+        assert_matches!(
+            get_ptr_from_reference(&vm, &hint_ref, &ApTracking::new()),
+            Ok(x) if x == relocatable!(4, 2)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_ptr_from_reference_with_dereference_and_imm() {
@@ -276,6 +473,28 @@ mod tests {
         assert!(compute_addr_from_reference(&hint_reference, &vm, &ApTracking::new()).is_none());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_addr_from_reference_immediate_offset2() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (4, 0))];
+        // Reference: [fp] + 3, with the constant offset encoded as an immediate
+        let mut hint_reference = HintReference::new(0, 0, true, false);
+        hint_reference.offset2 = OffsetValue::Immediate(Felt252::from(3));
+
+        assert_matches!(
+            compute_addr_from_reference(&hint_reference, &vm, &ApTracking::new()),
+            Some(x) if x == relocatable!(4, 3)
+        );
+
+        // An immediate that doesn't fit in an offset can't produce an address
+        hint_reference.offset2 = OffsetValue::Immediate(Felt252::from(-1));
+        assert_matches!(
+            compute_addr_from_reference(&hint_reference, &vm, &ApTracking::new()),
+            None
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn compute_addr_from_reference_failed_to_get_ids() {
@@ -291,6 +510,29 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn felt_to_isize_small_positive() {
+        assert_matches!(felt_to_isize(&Felt252::from(17)), Ok(17));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn felt_to_isize_small_negative() {
+        // PRIME - 1
+        assert_matches!(felt_to_isize(&Felt252::MAX), Ok(-1));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn felt_to_isize_out_of_range() {
+        let felt = Felt252::from(isize::MAX) + 1;
+        assert_matches!(
+            felt_to_isize(&felt),
+            Err(VirtualMachineError::BigintToIsizeFail(bx)) if *bx == felt
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn tracking_correction_valid() {
@@ -6,7 +6,8 @@
 //!    - [`Hooks`](crate::vm::hooks::Hooks) support for the [VirtualMachine](vm::vm_core::VirtualMachine);
 //!    - the `print_*` family of hints;
 //!    - the `skip_next_instruction()` hints;
-//!    - implementations of [`arbitrary::Arbitrary`](https://docs.rs/arbitrary/latest/arbitrary/) for some structs.
+//!    - implementations of [`arbitrary::Arbitrary`](https://docs.rs/arbitrary/latest/arbitrary/) for some structs;
+//!    - random value generators for property testing in [`test_utils`].
 //! - `cairo-1-hints`: Enable hints that were introduced in Cairo 1. Not enabled by default.
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -59,6 +60,9 @@ pub mod hint_processor;
 pub mod math_utils;
 pub mod program_hash;
 pub mod serde;
+#[cfg(feature = "test_utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test_utils")))]
+pub mod test_utils;
 pub mod types;
 pub mod utils;
 pub mod vm;
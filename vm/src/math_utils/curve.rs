@@ -0,0 +1,36 @@
+//! Constants of the STARK curve y^2 = x^3 + alpha * x + beta, used by the ec_op builtin
+//! and the signature hints.
+
+use crate::Felt252;
+
+/// `alpha` coefficient of the STARK curve
+pub const STARK_ALPHA: Felt252 = Felt252::ONE;
+
+/// `beta` coefficient of the STARK curve
+pub const STARK_BETA: Felt252 = Felt252::from_hex_unchecked(
+    "0x6f21413efbe40de150e596d72f7a8c5609ad26c15c915c1f4cdfcb99cee9e89",
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn stark_beta_value() {
+        let beta_low = Felt252::from(0x609ad26c15c915c1f4cdfcb99cee9e89_u128);
+        let beta_high = Felt252::from(0x6f21413efbe40de150e596d72f7a8c5_u128);
+        let beta = beta_high * Felt252::TWO.pow(128_u32) + beta_low;
+        assert_eq!(beta, STARK_BETA);
+        assert_eq!(
+            STARK_BETA,
+            Felt252::from_dec_str(
+                "3141592653589793238462643383279502884197169399375105820974944592307816406665"
+            )
+            .unwrap()
+        );
+    }
+}
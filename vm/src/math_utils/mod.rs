@@ -1,3 +1,4 @@
+pub mod curve;
 mod is_prime;
 
 pub use is_prime::is_prime;
@@ -95,9 +96,15 @@ pub fn isqrt(n: &BigUint) -> Result<BigUint, MathError> {
     Ok(x)
 }
 
+/// Performs integer division between x and y, returning the quotient and the remainder.
+/// Only fails if y is zero.
+pub fn div_with_remainder(x: &Felt252, y: &Felt252) -> Result<(Felt252, Felt252), MathError> {
+    Ok(x.div_rem(&y.try_into().map_err(|_| MathError::DividedByZero)?))
+}
+
 /// Performs integer division between x and y; fails if x is not divisible by y.
 pub fn safe_div(x: &Felt252, y: &Felt252) -> Result<Felt252, MathError> {
-    let (q, r) = x.div_rem(&y.try_into().map_err(|_| MathError::DividedByZero)?);
+    let (q, r) = div_with_remainder(x, y)?;
 
     if !r.is_zero() {
         Err(MathError::SafeDivFail(Box::new((*x, *y))))
@@ -196,6 +203,20 @@ pub fn div_mod(n: &BigInt, m: &BigInt, p: &BigInt) -> Result<BigInt, MathError>
     Ok((n * a).mod_floor(p))
 }
 
+/// Computes base**exp mod modulus, with the result in [0, modulus) for a positive modulus.
+/// Negative exponents are computed using the modular inverse of base.
+/// Fails if modulus is zero, or if exp is negative and base has no inverse modulo modulus.
+pub fn pow_mod(base: &BigInt, exp: &BigInt, modulus: &BigInt) -> Result<BigInt, MathError> {
+    if modulus.is_zero() {
+        return Err(MathError::DividedByZero);
+    }
+    if exp.is_negative() {
+        let base_inv = div_mod(&BigInt::one(), base, modulus)?;
+        return Ok(base_inv.modpow(&-exp, modulus));
+    }
+    Ok(base.mod_floor(modulus).modpow(exp, modulus))
+}
+
 pub(crate) fn div_mod_unsigned(
     n: &BigUint,
     m: &BigUint,
@@ -210,6 +231,48 @@ pub(crate) fn div_mod_unsigned(
     .map(|i| i.to_biguint().unwrap())
 }
 
+/// Felt252 version of [`div_mod`], taking each operand as its canonical representative in [0, PRIME).
+/// Fails if p is zero or if m has no inverse modulo p.
+pub fn div_mod_felt(n: &Felt252, m: &Felt252, p: &Felt252) -> Result<Felt252, MathError> {
+    if p == &Felt252::ZERO {
+        return Err(MathError::DividedByZero);
+    }
+    // The result is lower than p, so it always fits in a Felt252
+    div_mod(&n.to_bigint(), &m.to_bigint(), &p.to_bigint()).map(|x| Felt252::from(&x))
+}
+
+/// Returns `(true, x)`, where `x` is the inverse of `value` modulo `modulus`, if `value` is
+/// invertible. Otherwise returns `(false, y)`, where `y = modulus / gcd(value, modulus)` is a
+/// nonzero nullifier of `value`, i.e. `(value * y) % modulus == 0`.
+/// `modulus` must be nonzero.
+///
+/// # Examples
+///
+/// ```
+/// # use cairo_vm::math_utils::modular_inverse_or_nullifier;
+/// # use num_bigint::BigUint;
+/// // 3 * 5 = 15 = 1 (mod 7)
+/// assert_eq!(
+///     modular_inverse_or_nullifier(&BigUint::from(3_u32), &BigUint::from(7_u32)),
+///     (true, BigUint::from(5_u32))
+/// );
+///
+/// // gcd(4, 6) = 2, and 4 * (6 / 2) = 12 = 0 (mod 6)
+/// assert_eq!(
+///     modular_inverse_or_nullifier(&BigUint::from(4_u32), &BigUint::from(6_u32)),
+///     (false, BigUint::from(3_u32))
+/// );
+/// ```
+pub fn modular_inverse_or_nullifier(value: &BigUint, modulus: &BigUint) -> (bool, BigUint) {
+    let gcd = value.gcd(modulus);
+    if !gcd.is_one() {
+        return (false, modulus / gcd);
+    }
+    let modulus = BigInt::from(modulus.clone());
+    let (x, _, _) = igcdex(&BigInt::from(value.clone()), &modulus);
+    (true, x.mod_floor(&modulus).magnitude().clone())
+}
+
 pub fn ec_add(
     point_a: (BigInt, BigInt),
     point_b: (BigInt, BigInt),
@@ -264,6 +327,92 @@ pub fn ec_double_slope(
     )
 }
 
+/// A point on an elliptic curve over GF(p), including the point at infinity (the identity
+/// element of the curve group).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EcPoint {
+    Affine((BigInt, BigInt)),
+    Infinity,
+}
+
+impl EcPoint {
+    /// Adds two EC points, handling the point at infinity and points sharing the same x
+    /// coordinate, which [`ec_add`] doesn't support.
+    pub fn add(
+        &self,
+        other: &EcPoint,
+        alpha: &BigInt,
+        prime: &BigInt,
+    ) -> Result<EcPoint, MathError> {
+        match (self, other) {
+            (EcPoint::Infinity, point) | (point, EcPoint::Infinity) => Ok(point.clone()),
+            (EcPoint::Affine(point_a), EcPoint::Affine(point_b)) => {
+                if !(&point_a.0 - &point_b.0).is_multiple_of(prime) {
+                    return Ok(EcPoint::Affine(ec_add(
+                        point_a.clone(),
+                        point_b.clone(),
+                        prime,
+                    )?));
+                }
+                if (&point_a.1 + &point_b.1).is_multiple_of(prime) {
+                    // P + (-P)
+                    return Ok(EcPoint::Infinity);
+                }
+                self.double(alpha, prime)
+            }
+        }
+    }
+
+    /// Doubles an EC point, handling the point at infinity and points of order two, which
+    /// [`ec_double`] doesn't support.
+    pub fn double(&self, alpha: &BigInt, prime: &BigInt) -> Result<EcPoint, MathError> {
+        match self {
+            EcPoint::Affine(point) if !point.1.is_multiple_of(prime) => {
+                Ok(EcPoint::Affine(ec_double(point.clone(), alpha, prime)?))
+            }
+            _ => Ok(EcPoint::Infinity),
+        }
+    }
+}
+
+impl From<(BigInt, BigInt)> for EcPoint {
+    fn from(point: (BigInt, BigInt)) -> Self {
+        EcPoint::Affine(point)
+    }
+}
+
+/// Felt version of [`ec_add`], over the STARK field.
+pub fn ec_add_felt(
+    point_a: (Felt252, Felt252),
+    point_b: (Felt252, Felt252),
+) -> Result<(Felt252, Felt252), MathError> {
+    let prime: BigInt = (*CAIRO_PRIME).clone().into();
+    let (x, y) = ec_add(
+        felt_point_to_bigint(point_a),
+        felt_point_to_bigint(point_b),
+        &prime,
+    )?;
+    Ok((Felt252::from(&x), Felt252::from(&y)))
+}
+
+/// Felt version of [`ec_double`], over the STARK field.
+pub fn ec_double_felt(
+    point: (Felt252, Felt252),
+    alpha: Felt252,
+) -> Result<(Felt252, Felt252), MathError> {
+    let prime: BigInt = (*CAIRO_PRIME).clone().into();
+    let (x, y) = ec_double(
+        felt_point_to_bigint(point),
+        &alpha.to_biguint().into(),
+        &prime,
+    )?;
+    Ok((Felt252::from(&x), Felt252::from(&y)))
+}
+
+fn felt_point_to_bigint(point: (Felt252, Felt252)) -> (BigInt, BigInt) {
+    (point.0.to_biguint().into(), point.1.to_biguint().into())
+}
+
 // Adapted from sympy _sqrt_prime_power with k == 1
 pub fn sqrt_prime_power(a: &BigUint, p: &BigUint) -> Option<BigUint> {
     if p.is_zero() || !is_prime(p) {
@@ -388,6 +537,7 @@ pub(crate) fn is_quad_residue(a: &BigUint, p: &BigUint) -> Result<bool, MathErro
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::felt_str;
     use crate::utils::test_utils::*;
     use crate::utils::CAIRO_PRIME;
     use assert_matches::assert_matches;
@@ -476,6 +626,37 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn div_mod_felt_matches_div_mod() {
+        let n = Felt252::from_hex_unchecked(
+            "0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca",
+        );
+        let m = Felt252::from_hex_unchecked(
+            "0x5668060aa49730b7be4801df46ec62de53ecd11abe43a32873000c36e8dc1f",
+        );
+        let p = Felt252::from(0xfffffffb_u64);
+        let expected = div_mod(&n.to_bigint(), &m.to_bigint(), &p.to_bigint()).unwrap();
+        assert_eq!(div_mod_felt(&n, &m, &p), Ok(Felt252::from(&expected)));
+        assert_eq!(
+            div_mod_felt(&Felt252::from(3), &Felt252::from(4), &Felt252::from(7)),
+            Ok(Felt252::from(6))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn div_mod_felt_non_invertible() {
+        assert_matches!(
+            div_mod_felt(&Felt252::from(3), &Felt252::from(6), &Felt252::from(9)),
+            Err(MathError::DivModIgcdexNotZero(_))
+        );
+        assert_matches!(
+            div_mod_felt(&Felt252::from(3), &Felt252::from(6), &Felt252::ZERO),
+            Err(MathError::DividedByZero)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn compute_safe_div() {
@@ -706,6 +887,137 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn ec_point_add_identity_rules() {
+        let prime: BigInt = (*CAIRO_PRIME).clone().into();
+        let alpha = bigint!(1);
+        let point = EcPoint::from((
+            bigint_str!(
+                "1937407885261715145522756206040455121546447384489085099828343908348117672673"
+            ),
+            bigint_str!(
+                "2010355627224183802477187221870580930152258042445852905639855522404179702985"
+            ),
+        ));
+        let EcPoint::Affine((x, y)) = point.clone() else {
+            unreachable!()
+        };
+        let neg_point = EcPoint::from((x, (-y).mod_floor(&prime)));
+
+        // P + infinity = P
+        assert_eq!(
+            point.add(&EcPoint::Infinity, &alpha, &prime).unwrap(),
+            point
+        );
+        assert_eq!(
+            EcPoint::Infinity.add(&point, &alpha, &prime).unwrap(),
+            point
+        );
+        // P + (-P) = infinity
+        assert_eq!(
+            point.add(&neg_point, &alpha, &prime).unwrap(),
+            EcPoint::Infinity
+        );
+        // P + P = 2P
+        assert_eq!(
+            point.add(&point, &alpha, &prime).unwrap(),
+            point.double(&alpha, &prime).unwrap()
+        );
+        assert_eq!(
+            EcPoint::Infinity.double(&alpha, &prime).unwrap(),
+            EcPoint::Infinity
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn calculate_ec_add_felt_for_valid_points_a() {
+        let point_a = (
+            felt_str!(
+                "1183418161532233795704555250127335895546712857142554564893196731153957537489"
+            ),
+            felt_str!(
+                "1938007580204102038458825306058547644691739966277761828724036384003180924526"
+            ),
+        );
+        let point_b = (
+            felt_str!(
+                "1977703130303461992863803129734853218488251484396280000763960303272760326570"
+            ),
+            felt_str!(
+                "2565191853811572867032277464238286011368568368717965689023024980325333517459"
+            ),
+        );
+        assert_eq!(
+            (
+                felt_str!(
+                    "1977874238339000383330315148209250828062304908491266318460063803060754089297"
+                ),
+                felt_str!(
+                    "2969386888251099938335087541720168257053975603483053253007176033556822156706"
+                )
+            ),
+            ec_add_felt(point_a, point_b).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn calculate_ec_add_felt_for_valid_points_b() {
+        let point_a = (
+            felt_str!(
+                "3139037544796708144595053687182055617920475701120786241351436619796497072089"
+            ),
+            felt_str!(
+                "2119589567875935397690285099786081818522144748339117565577200220779667999801"
+            ),
+        );
+        let point_b = (
+            felt_str!(
+                "3324833730090626974525872402899302150520188025637965566623476530814354734325"
+            ),
+            felt_str!(
+                "3147007486456030910661996439995670279305852583596209647900952752170983517249"
+            ),
+        );
+        assert_eq!(
+            (
+                felt_str!(
+                    "1183418161532233795704555250127335895546712857142554564893196731153957537489"
+                ),
+                felt_str!(
+                    "1938007580204102038458825306058547644691739966277761828724036384003180924526"
+                )
+            ),
+            ec_add_felt(point_a, point_b).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn calculate_ec_double_felt_for_valid_point_a() {
+        let point = (
+            felt_str!(
+                "1937407885261715145522756206040455121546447384489085099828343908348117672673"
+            ),
+            felt_str!(
+                "2010355627224183802477187221870580930152258042445852905639855522404179702985"
+            ),
+        );
+        assert_eq!(
+            (
+                felt_str!(
+                    "58460926014232092148191979591712815229424797874927791614218178721848875644"
+                ),
+                felt_str!(
+                    "1065613861227134732854284722490492186040898336012372352512913425790457998694"
+                )
+            ),
+            ec_double_felt(point, Felt252::ONE).unwrap()
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn calculate_ec_add_for_valid_points_b() {
@@ -802,6 +1114,80 @@ mod tests {
         assert_matches!(isqrt(&n), Ok(inner) if inner.is_zero());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn pow_mod_positive_exponent() {
+        assert_eq!(
+            pow_mod(&bigint!(4), &bigint!(13), &bigint!(497)),
+            Ok(bigint!(445))
+        );
+        assert_eq!(
+            pow_mod(&bigint!(-4), &bigint!(3), &bigint!(497)),
+            Ok(bigint!(433))
+        );
+        assert_eq!(
+            pow_mod(&bigint!(7), &bigint!(0), &bigint!(13)),
+            Ok(bigint!(1))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn pow_mod_negative_exponent() {
+        // 3 * 5 = 15 = 1 (mod 7), so 3**-2 = 5**2 = 4 (mod 7)
+        assert_eq!(
+            pow_mod(&bigint!(3), &bigint!(-2), &bigint!(7)),
+            Ok(bigint!(4))
+        );
+        // 2 has no inverse modulo 4
+        assert_matches!(
+            pow_mod(&bigint!(2), &bigint!(-1), &bigint!(4)),
+            Err(MathError::DivModIgcdexNotZero(_))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn pow_mod_zero_modulus() {
+        assert_matches!(
+            pow_mod(&bigint!(3), &bigint!(2), &bigint!(0)),
+            Err(MathError::DividedByZero)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn div_with_remainder_exact() {
+        let x = Felt252::from(42);
+        let y = Felt252::from(6);
+        assert_eq!(
+            div_with_remainder(&x, &y),
+            Ok((Felt252::from(7), Felt252::ZERO))
+        );
+        assert_eq!(safe_div(&x, &y), Ok(Felt252::from(7)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn div_with_remainder_inexact() {
+        let x = Felt252::from(44);
+        let y = Felt252::from(6);
+        assert_eq!(
+            div_with_remainder(&x, &y),
+            Ok((Felt252::from(7), Felt252::from(2)))
+        );
+        assert_matches!(safe_div(&x, &y), Err(MathError::SafeDivFail(bx)) if *bx == (x, y));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn div_with_remainder_by_zero() {
+        assert_matches!(
+            div_with_remainder(&Felt252::ONE, &Felt252::ZERO),
+            Err(MathError::DividedByZero)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn safe_div_bigint_by_zero() {
@@ -424,6 +424,20 @@ pub fn deserialize_program_json(reader: &[u8]) -> Result<ProgramJson, ProgramErr
     let program_json = serde_json::from_slice(reader)?;
     Ok(program_json)
 }
+
+/// Returns the sorted names of the functions of the `__main__` module, which can be used as entrypoints.
+pub(crate) fn get_entrypoint_names(identifiers: &HashMap<String, Identifier>) -> Vec<String> {
+    let mut names: Vec<String> = identifiers
+        .iter()
+        .filter(|(_, identifier)| identifier.type_.as_deref() == Some("function"))
+        .filter_map(|(name, _)| name.strip_prefix("__main__."))
+        .filter(|name| !name.contains('.'))
+        .map(String::from)
+        .collect();
+    names.sort();
+    names
+}
+
 pub fn deserialize_and_parse_program(
     reader: &[u8],
     entrypoint: Option<&str>,
@@ -446,7 +460,12 @@ pub fn parse_program_json(
             .get(&format!("__main__.{entrypoint}"))
         {
             Some(entrypoint_identifier) => entrypoint_identifier.pc,
-            None => return Err(ProgramError::EntrypointNotFound(entrypoint.to_string())),
+            None => {
+                return Err(ProgramError::EntrypointNotFound(
+                    entrypoint.to_string(),
+                    get_entrypoint_names(&program_json.identifiers),
+                ))
+            }
         },
         None => None,
     };
@@ -846,7 +865,8 @@ mod tests {
         assert!(deserialization_result.is_err());
         assert_matches!(
             deserialization_result,
-            Err(ProgramError::EntrypointNotFound(_))
+            Err(ProgramError::EntrypointNotFound(name, available))
+            if name == "missing_function" && available == vec!["main".to_string()]
         );
     }
 
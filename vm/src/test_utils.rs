@@ -0,0 +1,50 @@
+//! Helpers to generate random values for property testing.
+//! Only available with the `test_utils` feature.
+
+use crate::math_utils::curve::STARK_BETA;
+use crate::utils::CAIRO_PRIME;
+use crate::Felt252;
+use num_bigint::RandBigInt;
+use rand::Rng;
+
+/// Returns a field element chosen uniformly at random
+pub fn random_felt(rng: &mut impl Rng) -> Felt252 {
+    Felt252::from(&rng.gen_biguint_below(&CAIRO_PRIME))
+}
+
+/// Returns a random point (x, y) on the STARK curve y^2 = x^3 + x + beta.
+/// Random x coordinates are sampled until one of them has a matching y coordinate.
+pub fn random_curve_point(rng: &mut impl Rng) -> (Felt252, Felt252) {
+    loop {
+        let x = random_felt(rng);
+        let y_squared = x * x * x + x + STARK_BETA;
+        if let Some(y) = y_squared.sqrt() {
+            // Both y and -y are valid, pick one at random
+            return if rng.gen() { (x, y) } else { (x, -y) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    #[test]
+    fn random_felt_is_deterministic_for_a_given_seed() {
+        let mut rng_a = SmallRng::seed_from_u64(42);
+        let mut rng_b = SmallRng::seed_from_u64(42);
+        for _ in 0..10 {
+            assert_eq!(random_felt(&mut rng_a), random_felt(&mut rng_b));
+        }
+    }
+
+    #[test]
+    fn random_curve_point_is_on_curve() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        for _ in 0..100 {
+            let (x, y) = random_curve_point(&mut rng);
+            assert_eq!(y * y, x * x * x + x + STARK_BETA);
+        }
+    }
+}
@@ -62,6 +62,10 @@ impl ResourceTracker for SimplifiedOsHintProcessor {
     fn run_resources(&self) -> &RunResources {
         &self.run_resources
     }
+
+    fn set_run_resources(&mut self, run_resources: RunResources) {
+        self.run_resources = run_resources;
+    }
 }
 
 impl Default for SimplifiedOsHintProcessor {
@@ -49,6 +49,23 @@ pub enum BuiltinName {
     mul_mod,
 }
 
+/// Builtins in the order in which the layouts create their runners.
+/// `segment_arena` is not part of any layout, so it is placed last.
+pub const BUILTIN_CANONICAL_ORDER: [BuiltinName; 12] = [
+    BuiltinName::output,
+    BuiltinName::pedersen,
+    BuiltinName::range_check,
+    BuiltinName::ecdsa,
+    BuiltinName::bitwise,
+    BuiltinName::ec_op,
+    BuiltinName::keccak,
+    BuiltinName::poseidon,
+    BuiltinName::range_check96,
+    BuiltinName::add_mod,
+    BuiltinName::mul_mod,
+    BuiltinName::segment_arena,
+];
+
 impl BuiltinName {
     /// Converts a [`BuiltinName`] to its string representation adding the "_builtin" suffix
     ///
@@ -136,6 +153,23 @@ impl BuiltinName {
         }
     }
 
+    /// Returns the position of the builtin in [`BUILTIN_CANONICAL_ORDER`]
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use cairo_vm::types::builtin_name::BuiltinName;
+    ///
+    /// assert!(BuiltinName::output.canonical_index() < BuiltinName::pedersen.canonical_index());
+    ///
+    /// ```
+    pub fn canonical_index(self) -> usize {
+        BUILTIN_CANONICAL_ORDER
+            .iter()
+            .position(|name| *name == self)
+            .expect("every builtin is part of the canonical order")
+    }
+
     // Implementing this as a trait would generate confusion as `Display` impl uses suffixed version
     #[allow(clippy::should_implement_trait)]
     /// Converts a [`BuiltinName`] from its string representation
@@ -179,9 +213,11 @@ impl core::fmt::Display for BuiltinName {
 // Implementation of custom serialization & deserialization for maps using builtin names with suffixes as keys
 pub(crate) mod serde_generic_map_impl {
     use super::BuiltinName;
-    use crate::stdlib::{collections::HashMap, string::String};
+    use crate::stdlib::{collections::HashMap, string::String, vec::Vec};
     use serde::{de::Error, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 
+    /// Entries are serialized following the canonical builtin order, so that the output doesn't
+    /// depend on the iteration order of the map
     pub fn serialize<S, V>(
         values: &HashMap<BuiltinName, V>,
         serializer: S,
@@ -190,8 +226,10 @@ pub(crate) mod serde_generic_map_impl {
         S: Serializer,
         V: Serialize,
     {
+        let mut entries: Vec<_> = values.iter().collect();
+        entries.sort_by_key(|(key, _)| key.canonical_index());
         let mut map_serializer = serializer.serialize_map(Some(values.len()))?;
-        for (key, val) in values {
+        for (key, val) in entries {
             map_serializer.serialize_entry(key.to_str_with_suffix(), val)?
         }
         map_serializer.end()
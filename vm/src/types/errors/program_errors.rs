@@ -9,8 +9,8 @@ pub enum ProgramError {
     IO(#[from] std::io::Error),
     #[error(transparent)]
     Parse(#[from] serde_json::Error),
-    #[error("Entrypoint {0} not found")]
-    EntrypointNotFound(String),
+    #[error("Entrypoint {} not found. Available entrypoints: [{}]", .0, .1.join(", "))]
+    EntrypointNotFound(String, Vec<String>),
     #[error("Constant {0} has no value")]
     ConstWithoutValue(String),
     #[error("Expected prime {PRIME_STR}, got {0}")]
@@ -19,6 +19,8 @@ pub enum ProgramError {
     StrippedProgramNoMain,
     #[error("Hint PC ({0}) is greater or equal to program length ({1})")]
     InvalidHintPc(usize, usize),
+    #[error("Main PC ({0}) is greater or equal to program length ({1})")]
+    InvalidMainPc(usize, usize),
 }
 
 #[cfg(test)]
@@ -31,8 +33,14 @@ mod tests {
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn format_entrypoint_not_found_error() {
-        let error = ProgramError::EntrypointNotFound(String::from("my_function"));
+        let error = ProgramError::EntrypointNotFound(
+            String::from("my_function"),
+            vec![String::from("main"), String::from("other_function")],
+        );
         let formatted_error = format!("{error}");
-        assert_eq!(formatted_error, "Entrypoint my_function not found");
+        assert_eq!(
+            formatted_error,
+            "Entrypoint my_function not found. Available entrypoints: [main, other_function]"
+        );
     }
 }
@@ -2,8 +2,39 @@ use crate::stdlib::{any::Any, cell::RefCell, collections::HashMap, prelude::*, r
 use crate::{
     any_box,
     hint_processor::builtin_hint_processor::dict_manager::DictManager,
+    types::relocatable::{MaybeRelocatable, Relocatable},
     vm::errors::{exec_scope_errors::ExecScopeError, hint_errors::HintError},
+    Felt252,
 };
+use num_bigint::{BigInt, BigUint};
+
+/// Clones a scope value if it holds the type handled by the cloner, returning None otherwise
+pub type ScopeValueCloner = fn(&dyn Any) -> Option<Box<dyn Any>>;
+
+/// [ScopeValueCloner] for values of type `T`, to be registered with [ExecutionScopes::deep_clone_with]
+pub fn clone_scope_value<T: Any + Clone>(value: &dyn Any) -> Option<Box<dyn Any>> {
+    value
+        .downcast_ref::<T>()
+        .map(|value| any_box!(value.clone()))
+}
+
+/// Cloners for the types commonly stored in the scopes by the builtin hints
+const DEFAULT_SCOPE_VALUE_CLONERS: &[ScopeValueCloner] = &[
+    clone_scope_value::<Felt252>,
+    clone_scope_value::<BigInt>,
+    clone_scope_value::<BigUint>,
+    clone_scope_value::<u64>,
+    clone_scope_value::<usize>,
+    clone_scope_value::<bool>,
+    clone_scope_value::<String>,
+    clone_scope_value::<Relocatable>,
+    clone_scope_value::<MaybeRelocatable>,
+    clone_scope_value::<Vec<Felt252>>,
+    clone_scope_value::<Vec<BigInt>>,
+    clone_scope_value::<Vec<u64>>,
+    clone_scope_value::<Vec<usize>>,
+    clone_scope_value::<HashMap<Felt252, Vec<Felt252>>>,
+];
 
 #[derive(Debug)]
 pub struct ExecutionScopes {
@@ -182,6 +213,41 @@ impl ExecutionScopes {
     pub fn insert_value<T: 'static>(&mut self, name: &str, value: T) {
         self.assign_or_update_variable(name, any_box!(value));
     }
+
+    ///Returns a copy of the scopes whose values are independent from the original ones.
+    ///Only values of the types commonly used by the builtin hints can be cloned,
+    ///see [ExecutionScopes::deep_clone_with] to handle other types
+    pub fn deep_clone(&self) -> Result<ExecutionScopes, ExecScopeError> {
+        self.deep_clone_with(&[])
+    }
+
+    ///Like [ExecutionScopes::deep_clone], trying the given cloners before the default ones.
+    ///Fails if a value isn't handled by any of them
+    pub fn deep_clone_with(
+        &self,
+        cloners: &[ScopeValueCloner],
+    ) -> Result<ExecutionScopes, ExecScopeError> {
+        let data = self
+            .data
+            .iter()
+            .map(|scope| {
+                scope
+                    .iter()
+                    .map(|(name, value)| {
+                        cloners
+                            .iter()
+                            .chain(DEFAULT_SCOPE_VALUE_CLONERS)
+                            .find_map(|cloner| cloner(value.as_ref()))
+                            .map(|value| (name.clone(), value))
+                            .ok_or_else(|| {
+                                ExecScopeError::UncloneableValue(name.clone().into_boxed_str())
+                            })
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(ExecutionScopes { data })
+    }
 }
 
 impl Default for ExecutionScopes {
@@ -193,7 +259,6 @@ impl Default for ExecutionScopes {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Felt252;
     use assert_matches::assert_matches;
 
     #[cfg(target_arch = "wasm32")]
@@ -377,6 +442,21 @@ mod tests {
             .contains_key(&String::from("a")));
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_deleted_variable_test() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_value("value", Felt252::from(2));
+        assert_matches!(scopes.get_ref::<Felt252>("value"), Ok(x) if *x == Felt252::from(2));
+
+        scopes.delete_variable("value");
+
+        assert_matches!(
+            scopes.get_ref::<Felt252>("value"),
+            Err(HintError::VariableNotInScopeError(bx)) if bx.as_ref() == "value"
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn exit_main_scope_gives_error_test() {
@@ -407,6 +487,35 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn push_to_scoped_list_across_hints() {
+        // Mimics a hint accumulating values in a scoped list across invocations
+        fn push_value(scopes: &mut ExecutionScopes, value: i32) -> Result<(), HintError> {
+            if scopes.get_list_ref::<BigInt>("values").is_err() {
+                scopes.insert_value("values", Vec::<BigInt>::new());
+            }
+            scopes
+                .get_mut_list_ref::<BigInt>("values")?
+                .push(BigInt::from(value));
+            Ok(())
+        }
+
+        let mut scopes = ExecutionScopes::new();
+        assert_matches!(push_value(&mut scopes, 3), Ok(()));
+        assert_matches!(push_value(&mut scopes, -7), Ok(()));
+
+        assert_eq!(
+            scopes.get_list_ref::<BigInt>("values").unwrap(),
+            &vec![BigInt::from(3), BigInt::from(-7)]
+        );
+        // The list can't be accessed as a list of a different type
+        assert_matches!(
+            scopes.get_list_ref::<u64>("values"),
+            Err(HintError::VariableNotInScopeError(x)) if *x == *"values"
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_u64_test() {
@@ -475,4 +584,44 @@ mod tests {
         assert!(scopes.get_any_boxed_mut("no_variable").is_err());
         assert!(scopes.get_any_boxed_ref("no_variable").is_err());
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deep_clone_scopes_are_independent() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_value("a", BigInt::from(2));
+        scopes.enter_scope(HashMap::new());
+        scopes.insert_value("b", Felt252::from(3));
+
+        let mut cloned = scopes.deep_clone().unwrap();
+        assert_eq!(cloned.data.len(), 2);
+
+        *scopes.get_mut_ref::<Felt252>("b").unwrap() = Felt252::from(30);
+        scopes.exit_scope().unwrap();
+        *scopes.get_mut_ref::<BigInt>("a").unwrap() = BigInt::from(20);
+
+        assert_eq!(cloned.get::<Felt252>("b").unwrap(), Felt252::from(3));
+        cloned.exit_scope().unwrap();
+        assert_eq!(cloned.get::<BigInt>("a").unwrap(), BigInt::from(2));
+        assert_eq!(scopes.get::<BigInt>("a").unwrap(), BigInt::from(20));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deep_clone_with_registered_cloner() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Custom(u8);
+
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_value("custom", Custom(1));
+        assert_eq!(
+            scopes.deep_clone().err(),
+            Some(ExecScopeError::UncloneableValue("custom".into()))
+        );
+
+        let cloned = scopes
+            .deep_clone_with(&[clone_scope_value::<Custom>])
+            .unwrap();
+        assert_eq!(cloned.get::<Custom>("custom").unwrap(), Custom(1));
+    }
 }
@@ -1,3 +1,5 @@
+use crate::stdlib::prelude::*;
+use crate::types::builtin_name::BuiltinName;
 use crate::types::layout::CairoLayoutParams;
 
 use super::mod_instance_def::ModInstanceDef;
@@ -13,7 +15,7 @@ pub(crate) const BUILTIN_INSTANCES_PER_COMPONENT: u32 = 1;
 
 use serde::Serialize;
 
-#[derive(Serialize, Debug, PartialEq)]
+#[derive(Serialize, Clone, Debug, PartialEq)]
 pub(crate) struct BuiltinsInstanceDef {
     pub(crate) output: bool,
     pub(crate) pedersen: Option<PedersenInstanceDef>,
@@ -29,6 +31,52 @@ pub(crate) struct BuiltinsInstanceDef {
 }
 
 impl BuiltinsInstanceDef {
+    /// Returns the included builtins in their canonical order, along with their ratios.
+    /// The output builtin has no ratio.
+    pub(crate) fn ratios(&self) -> Vec<(BuiltinName, Option<u32>)> {
+        let mut ratios = Vec::new();
+        if self.output {
+            ratios.push((BuiltinName::output, None));
+        }
+        if let Some(pedersen) = &self.pedersen {
+            ratios.push((BuiltinName::pedersen, pedersen.ratio));
+        }
+        if let Some(range_check) = &self.range_check {
+            ratios.push((
+                BuiltinName::range_check,
+                range_check.ratio.map(|r| r.numerator),
+            ));
+        }
+        if let Some(ecdsa) = &self.ecdsa {
+            ratios.push((BuiltinName::ecdsa, ecdsa.ratio));
+        }
+        if let Some(bitwise) = &self.bitwise {
+            ratios.push((BuiltinName::bitwise, bitwise.ratio));
+        }
+        if let Some(ec_op) = &self.ec_op {
+            ratios.push((BuiltinName::ec_op, ec_op.ratio));
+        }
+        if let Some(keccak) = &self.keccak {
+            ratios.push((BuiltinName::keccak, keccak.ratio));
+        }
+        if let Some(poseidon) = &self.poseidon {
+            ratios.push((BuiltinName::poseidon, poseidon.ratio));
+        }
+        if let Some(range_check96) = &self.range_check96 {
+            ratios.push((
+                BuiltinName::range_check96,
+                range_check96.ratio.map(|r| r.numerator),
+            ));
+        }
+        if let Some(add_mod) = &self.add_mod {
+            ratios.push((BuiltinName::add_mod, add_mod.ratio.map(|r| r.numerator)));
+        }
+        if let Some(mul_mod) = &self.mul_mod {
+            ratios.push((BuiltinName::mul_mod, mul_mod.ratio.map(|r| r.numerator)));
+        }
+        ratios
+    }
+
     pub(crate) fn plain() -> BuiltinsInstanceDef {
         BuiltinsInstanceDef {
             output: false,
@@ -100,7 +148,7 @@ impl BuiltinsInstanceDef {
             range_check: Some(RangeCheckInstanceDef::new(Some(16))),
             ecdsa: Some(EcdsaInstanceDef::new(Some(2048))),
             bitwise: Some(BitwiseInstanceDef::new(Some(64))),
-            ec_op: Some(EcOpInstanceDef::new(Some(1024))),
+            ec_op: Some(EcOpInstanceDef::new(Some(1024), None)),
             keccak: None,
             poseidon: Some(PoseidonInstanceDef::default()),
             range_check96: None,
@@ -116,7 +164,7 @@ impl BuiltinsInstanceDef {
             range_check: Some(RangeCheckInstanceDef::new(Some(16))),
             ecdsa: Some(EcdsaInstanceDef::new(Some(2048))),
             bitwise: Some(BitwiseInstanceDef::new(Some(64))),
-            ec_op: Some(EcOpInstanceDef::new(Some(1024))),
+            ec_op: Some(EcOpInstanceDef::new(Some(1024), None)),
             keccak: Some(KeccakInstanceDef::new(Some(2048))),
             poseidon: Some(PoseidonInstanceDef::default()),
             range_check96: None,
@@ -164,7 +212,7 @@ impl BuiltinsInstanceDef {
             range_check: Some(RangeCheckInstanceDef::default()),
             ecdsa: Some(EcdsaInstanceDef::new(Some(2048))),
             bitwise: Some(BitwiseInstanceDef::new(Some(16))),
-            ec_op: Some(EcOpInstanceDef::new(Some(1024))),
+            ec_op: Some(EcOpInstanceDef::new(Some(1024), None)),
             keccak: Some(KeccakInstanceDef::new(Some(2048))),
             poseidon: Some(PoseidonInstanceDef::new(Some(256))),
             range_check96: Some(RangeCheckInstanceDef::new(Some(8))),
@@ -208,9 +256,10 @@ impl BuiltinsInstanceDef {
         let bitwise = Some(BitwiseInstanceDef {
             ratio: Some(params.bitwise_ratio),
         });
-        let ec_op = Some(EcOpInstanceDef {
-            ratio: Some(params.ec_op_ratio),
-        });
+        let ec_op = Some(EcOpInstanceDef::new(
+            Some(params.ec_op_ratio),
+            params.ec_op_scalar_height,
+        ));
         let keccak = Some(KeccakInstanceDef {
             ratio: Some(params.keccak_ratio),
         });
@@ -5,19 +5,29 @@ pub(crate) const INPUT_CELLS_PER_EC_OP: u32 = 5;
 pub(crate) const SCALAR_HEIGHT: u32 = 256;
 
 #[derive(Serialize, Clone, Debug, PartialEq)]
-pub(crate) struct EcOpInstanceDef {
+pub struct EcOpInstanceDef {
     pub(crate) ratio: Option<u32>,
+    /// Number of bits of the scalar m processed by the builtin
+    pub(crate) scalar_height: u32,
 }
 
 impl Default for EcOpInstanceDef {
     fn default() -> Self {
-        EcOpInstanceDef { ratio: Some(256) }
+        EcOpInstanceDef {
+            ratio: Some(256),
+            scalar_height: SCALAR_HEIGHT,
+        }
     }
 }
 
 impl EcOpInstanceDef {
-    pub(crate) fn new(ratio: Option<u32>) -> Self {
-        EcOpInstanceDef { ratio }
+    /// Creates the ec_op instance definition for the given ratio. The scalar height defaults to
+    /// 256 bits if not set.
+    pub fn new(ratio: Option<u32>, scalar_height: Option<u32>) -> Self {
+        EcOpInstanceDef {
+            ratio,
+            scalar_height: scalar_height.unwrap_or(SCALAR_HEIGHT),
+        }
     }
 }
 
@@ -31,14 +41,30 @@ mod tests {
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_new() {
-        let builtin_instance = EcOpInstanceDef { ratio: Some(8) };
-        assert_eq!(EcOpInstanceDef::new(Some(8)), builtin_instance);
+        let builtin_instance = EcOpInstanceDef {
+            ratio: Some(8),
+            scalar_height: 256,
+        };
+        assert_eq!(EcOpInstanceDef::new(Some(8), None), builtin_instance);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_new_with_scalar_height() {
+        let builtin_instance = EcOpInstanceDef {
+            ratio: Some(8),
+            scalar_height: 8,
+        };
+        assert_eq!(EcOpInstanceDef::new(Some(8), Some(8)), builtin_instance);
     }
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_default() {
-        let builtin_instance = EcOpInstanceDef { ratio: Some(256) };
+        let builtin_instance = EcOpInstanceDef {
+            ratio: Some(256),
+            scalar_height: 256,
+        };
         assert_eq!(EcOpInstanceDef::default(), builtin_instance);
     }
 }
@@ -13,7 +13,7 @@ pub(crate) const DEFAULT_CPU_COMPONENT_STEP: u32 = 1;
 
 use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Clone, Debug)]
 pub struct CairoLayout {
     pub(crate) name: LayoutName,
     pub(crate) cpu_component_step: u32,
@@ -163,7 +163,7 @@ impl CairoLayout {
 use arbitrary::{self, Arbitrary};
 
 #[cfg_attr(feature = "test_utils", derive(Arbitrary))]
-#[derive(Deserialize, Debug, Clone, Default)]
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 #[serde(try_from = "RawCairoLayoutParams")]
 pub struct CairoLayoutParams {
     pub rc_units: u32,
@@ -175,6 +175,8 @@ pub struct CairoLayoutParams {
     pub ecdsa_ratio: u32,
     pub bitwise_ratio: u32,
     pub ec_op_ratio: u32,
+    /// Number of bits of the scalars processed by the ec_op builtin, 256 if not set
+    pub ec_op_scalar_height: Option<u32>,
     pub keccak_ratio: u32,
     pub poseidon_ratio: u32,
     pub range_check96_ratio: u32,
@@ -219,6 +221,8 @@ pub struct RawCairoLayoutParams {
     #[serde(deserialize_with = "bool_from_int_or_bool")]
     pub uses_ec_op_builtin: bool,
     pub ec_op_ratio: u32,
+    #[serde(default)]
+    pub ec_op_scalar_height: Option<u32>,
     #[serde(deserialize_with = "bool_from_int_or_bool")]
     pub uses_keccak_builtin: bool,
     pub keccak_ratio: u32,
@@ -307,6 +311,7 @@ impl TryFrom<RawCairoLayoutParams> for CairoLayoutParams {
             ecdsa_ratio: value.ecdsa_ratio,
             bitwise_ratio: value.bitwise_ratio,
             ec_op_ratio: value.ec_op_ratio,
+            ec_op_scalar_height: value.ec_op_scalar_height,
             keccak_ratio: value.keccak_ratio,
             poseidon_ratio: value.poseidon_ratio,
             range_check96_ratio: value.range_check96_ratio,
@@ -483,6 +488,7 @@ mod tests {
             ecdsa_ratio: 32,
             bitwise_ratio: 32,
             ec_op_ratio: 32,
+            ec_op_scalar_height: None,
             keccak_ratio: 32,
             poseidon_ratio: 0,
             range_check96_ratio: 8,
@@ -529,7 +535,7 @@ mod tests {
         );
         assert_eq!(
             layout.builtins.ec_op,
-            Some(EcOpInstanceDef { ratio: Some(32) })
+            Some(EcOpInstanceDef::new(Some(32), None))
         );
         assert_eq!(
             layout.builtins.keccak,
@@ -610,6 +616,18 @@ mod tests {
         }\n\
         ";
 
-        serde_json::from_str::<CairoLayoutParams>(cairo_layout_params_json).unwrap();
+        let params = serde_json::from_str::<CairoLayoutParams>(cairo_layout_params_json).unwrap();
+        assert_eq!(params.ec_op_scalar_height, None);
+
+        let cairo_layout_params_json = cairo_layout_params_json.replace(
+            "\"ec_op_ratio\": 1024,",
+            "\"ec_op_ratio\": 1024, \"ec_op_scalar_height\": 128,",
+        );
+        let params = serde_json::from_str::<CairoLayoutParams>(&cairo_layout_params_json).unwrap();
+        assert_eq!(params.ec_op_scalar_height, Some(128));
+        assert_eq!(
+            CairoLayout::dynamic_instance(params).builtins.ec_op,
+            Some(EcOpInstanceDef::new(Some(1024), Some(128)))
+        );
     }
 }
@@ -1,3 +1,6 @@
+use crate::stdlib::prelude::*;
+use crate::types::builtin_name::BuiltinName;
+use crate::types::instance_definitions::builtins_instance_def::BuiltinsInstanceDef;
 #[cfg(feature = "test_utils")]
 use arbitrary::{self, Arbitrary};
 #[cfg(all(feature = "clap", feature = "std"))]
@@ -39,6 +42,88 @@ impl LayoutName {
             LayoutName::dynamic => "dynamic",
         }
     }
+
+    /// Returns the builtins included in the layout, in their canonical order.
+    /// The dynamic layout includes every builtin, with ratios given by its `CairoLayoutParams`.
+    pub fn builtins(&self) -> &'static [BuiltinName] {
+        use BuiltinName::*;
+        match self {
+            LayoutName::plain => &[],
+            LayoutName::small | LayoutName::dex => &[output, pedersen, range_check, ecdsa],
+            LayoutName::recursive => &[output, pedersen, range_check, bitwise],
+            LayoutName::starknet => &[
+                output,
+                pedersen,
+                range_check,
+                ecdsa,
+                bitwise,
+                ec_op,
+                poseidon,
+            ],
+            LayoutName::starknet_with_keccak => &[
+                output,
+                pedersen,
+                range_check,
+                ecdsa,
+                bitwise,
+                ec_op,
+                keccak,
+                poseidon,
+            ],
+            LayoutName::recursive_large_output | LayoutName::recursive_with_poseidon => {
+                &[output, pedersen, range_check, bitwise, poseidon]
+            }
+            LayoutName::all_solidity => &[output, pedersen, range_check, ecdsa, bitwise, ec_op],
+            #[cfg(feature = "mod_builtin")]
+            LayoutName::all_cairo | LayoutName::dynamic => &[
+                output,
+                pedersen,
+                range_check,
+                ecdsa,
+                bitwise,
+                ec_op,
+                keccak,
+                poseidon,
+                range_check96,
+                add_mod,
+                mul_mod,
+            ],
+            #[cfg(not(feature = "mod_builtin"))]
+            LayoutName::all_cairo | LayoutName::dynamic => &[
+                output,
+                pedersen,
+                range_check,
+                ecdsa,
+                bitwise,
+                ec_op,
+                keccak,
+                poseidon,
+                range_check96,
+            ],
+        }
+    }
+
+    /// Returns the builtins included in the layout along with their ratios, in their canonical order.
+    /// The ratio is `None` for the output builtin, and for every builtin of the dynamic layout,
+    /// as those depend on its `CairoLayoutParams`.
+    pub fn ratios(&self) -> Vec<(BuiltinName, Option<u32>)> {
+        let builtins = match self {
+            LayoutName::plain => BuiltinsInstanceDef::plain(),
+            LayoutName::small => BuiltinsInstanceDef::small(),
+            LayoutName::dex => BuiltinsInstanceDef::dex(),
+            LayoutName::recursive => BuiltinsInstanceDef::recursive(),
+            LayoutName::starknet => BuiltinsInstanceDef::starknet(),
+            LayoutName::starknet_with_keccak => BuiltinsInstanceDef::starknet_with_keccak(),
+            LayoutName::recursive_large_output => BuiltinsInstanceDef::recursive_large_output(),
+            LayoutName::recursive_with_poseidon => BuiltinsInstanceDef::recursive_with_poseidon(),
+            LayoutName::all_solidity => BuiltinsInstanceDef::all_solidity(),
+            LayoutName::all_cairo => BuiltinsInstanceDef::all_cairo(),
+            LayoutName::dynamic => {
+                return self.builtins().iter().map(|name| (*name, None)).collect()
+            }
+        };
+        builtins.ratios()
+    }
 }
 
 impl Display for LayoutName {
@@ -69,3 +154,50 @@ impl ValueEnum for LayoutName {
         Some(PossibleValue::new(self.to_str()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn layout_builtins() {
+        assert!(LayoutName::starknet_with_keccak
+            .builtins()
+            .contains(&BuiltinName::keccak));
+        assert!(LayoutName::plain.builtins().is_empty());
+        assert_eq!(
+            LayoutName::recursive.ratios(),
+            vec![
+                (BuiltinName::output, None),
+                (BuiltinName::pedersen, Some(128)),
+                (BuiltinName::range_check, Some(8)),
+                (BuiltinName::bitwise, Some(8)),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn layout_builtins_match_ratios() {
+        for layout in [
+            LayoutName::plain,
+            LayoutName::small,
+            LayoutName::dex,
+            LayoutName::recursive,
+            LayoutName::starknet,
+            LayoutName::starknet_with_keccak,
+            LayoutName::recursive_large_output,
+            LayoutName::recursive_with_poseidon,
+            LayoutName::all_solidity,
+            LayoutName::all_cairo,
+            LayoutName::dynamic,
+        ] {
+            let names: Vec<BuiltinName> = layout.ratios().into_iter().map(|(n, _)| n).collect();
+            assert_eq!(names, layout.builtins(), "{layout}");
+        }
+    }
+}
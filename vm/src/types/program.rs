@@ -8,7 +8,7 @@ use crate::{
         prelude::*,
         sync::Arc,
     },
-    vm::runners::cairo_pie::StrippedProgram,
+    vm::runners::cairo_pie::{CairoPie, StrippedProgram},
 };
 
 #[cfg(feature = "cairo-1-hints")]
@@ -274,12 +274,48 @@ impl Program {
         })
     }
 
+    /// Builds a minimal program from raw bytecode, without hints, identifiers or debug info.
+    /// Fails if `main` is outside of `data`.
+    pub fn from_parts(
+        data: Vec<MaybeRelocatable>,
+        builtins: Vec<BuiltinName>,
+        main: Option<usize>,
+    ) -> Result<Program, ProgramError> {
+        if let Some(main) = main.filter(|main| *main >= data.len()) {
+            return Err(ProgramError::InvalidMainPc(main, data.len()));
+        }
+        Self::new(
+            builtins,
+            data,
+            main,
+            HashMap::new(),
+            ReferenceManager::default(),
+            HashMap::new(),
+            Vec::new(),
+            None,
+        )
+    }
+
     #[cfg(feature = "std")]
     pub fn from_file(path: &Path, entrypoint: Option<&str>) -> Result<Program, ProgramError> {
         let file_content = std::fs::read(path)?;
         deserialize_and_parse_program(&file_content, entrypoint)
     }
 
+    /// Replaces the program's hints with the ones in the JSON file at `path`, which maps each pc
+    /// to its hints in the same format as the `hints` field of a compiled program.
+    /// Allows running programs whose bytecode and hints are distributed separately.
+    #[cfg(feature = "std")]
+    pub fn load_hints_from(&mut self, path: &Path) -> Result<(), ProgramError> {
+        let file_content = std::fs::read(path)?;
+        let hints: BTreeMap<usize, Vec<HintParams>> = serde_json::from_slice(&file_content)?;
+        let hints_collection = HintsCollection::new(&hints, self.shared_program_data.data.len())?;
+        Arc::make_mut(&mut self.shared_program_data).hints_collection = hints_collection;
+        Ok(())
+    }
+
+    /// Only programs compiled for the Stark prime are supported: fails with
+    /// [ProgramError::PrimeDiffers] if the program declares any other prime.
     pub fn from_bytes(bytes: &[u8], entrypoint: Option<&str>) -> Result<Program, ProgramError> {
         deserialize_and_parse_program(bytes, entrypoint)
     }
@@ -305,6 +341,12 @@ impl Program {
         self.builtins.len()
     }
 
+    /// Returns true if the program declares the same builtins, in the same order, as the
+    /// program the Cairo PIE was generated from.
+    pub fn builtins_match(&self, pie: &CairoPie) -> bool {
+        self.builtins == pie.builtins()
+    }
+
     pub fn get_identifier(&self, id: &str) -> Option<&Identifier> {
         self.shared_program_data.identifiers.get(id)
     }
@@ -608,6 +650,41 @@ mod tests {
             .is_empty());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn from_parts() {
+        use crate::cairo_run::{cairo_run_program, CairoRunConfig};
+        use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
+
+        // [ap] = [fp - 3], ap++; ret
+        let data = vec![
+            mayberelocatable!(5193354047062507520),
+            mayberelocatable!(2345108766317314046),
+        ];
+        assert_matches!(
+            Program::from_parts(data.clone(), vec![BuiltinName::output], Some(2)),
+            Err(ProgramError::InvalidMainPc(2, 2))
+        );
+
+        let program =
+            Program::from_parts(data.clone(), vec![BuiltinName::output], Some(0)).unwrap();
+        assert_eq!(program.shared_program_data.data, data);
+        assert_eq!(program.shared_program_data.main, Some(0));
+        assert_eq!(program.builtins, vec![BuiltinName::output]);
+
+        let cairo_run_config = CairoRunConfig {
+            layout: crate::types::layout_name::LayoutName::small,
+            ..Default::default()
+        };
+        let runner = cairo_run_program(
+            &program,
+            &cairo_run_config,
+            &mut BuiltinHintProcessor::new_empty(),
+        )
+        .unwrap();
+        assert_eq!(runner.vm.current_step, 2);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn new_program_with_hints() {
@@ -1426,6 +1503,21 @@ mod tests {
         assert_eq!(program, Program::default());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn from_bytes_prime_differs() {
+        let program_content =
+            include_bytes!("../../../cairo_programs/manually_compiled/valid_program_a.json");
+        let other_prime = "0xffffffff00000001000000000000000000000000ffffffffffffffffffffffff";
+        let program_content = String::from_utf8(program_content.to_vec())
+            .unwrap()
+            .replace(PRIME_STR, other_prime);
+        assert_matches!(
+            Program::from_bytes(program_content.as_bytes(), Some("main")),
+            Err(ProgramError::PrimeDiffers(prime)) if prime == other_prime
+        );
+    }
+
     #[test]
     fn get_stripped_program() {
         let program_content = include_bytes!("../../../cairo_programs/pedersen_test.json");
@@ -1449,4 +1541,54 @@ mod tests {
             Err(ProgramError::StrippedProgramNoMain)
         );
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn load_hints_from_sidecar_file() {
+        use crate::cairo_run::{cairo_run_program, CairoRunConfig};
+        use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
+        use crate::types::layout_name::LayoutName;
+
+        let program_content = include_bytes!("../../../cairo_programs/assert_nn.json");
+        let mut program = Program::from_bytes(program_content, Some("main")).unwrap();
+        let hints =
+            BTreeMap::<usize, Vec<HintParams>>::from(&program.shared_program_data.hints_collection);
+        assert!(!hints.is_empty());
+
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!(
+            "load_hints_from_sidecar_file_{}_{nanos}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, serde_json::to_vec(&hints).unwrap()).unwrap();
+
+        // Strip the program's hints
+        Arc::make_mut(&mut program.shared_program_data).hints_collection =
+            HintsCollection::default();
+        assert_eq!(
+            program.shared_program_data.hints_collection.iter().count(),
+            0
+        );
+
+        program.load_hints_from(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            BTreeMap::<usize, Vec<HintParams>>::from(&program.shared_program_data.hints_collection),
+            hints
+        );
+
+        let cairo_run_config = CairoRunConfig {
+            layout: LayoutName::all_cairo,
+            ..Default::default()
+        };
+        assert!(cairo_run_program(
+            &program,
+            &cairo_run_config,
+            &mut BuiltinHintProcessor::new_empty()
+        )
+        .is_ok());
+    }
 }
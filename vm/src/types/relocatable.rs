@@ -6,7 +6,9 @@ use crate::stdlib::{
 
 use crate::Felt252;
 use crate::{
-    relocatable, types::errors::math_errors::MathError, vm::errors::memory_errors::MemoryError,
+    relocatable,
+    types::errors::math_errors::MathError,
+    vm::errors::{memory_errors::MemoryError, runner_errors::RunnerError},
 };
 use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
@@ -110,6 +112,18 @@ impl Display for Relocatable {
     }
 }
 
+impl Relocatable {
+    /// Moves the offset by a signed delta, keeping the segment index.
+    /// Returns None if the resulting offset would be negative or exceed usize::MAX
+    pub fn offset_by_checked(&self, delta: i64) -> Option<Relocatable> {
+        let delta = isize::try_from(delta).ok()?;
+        Some(Relocatable {
+            segment_index: self.segment_index,
+            offset: self.offset.checked_add_signed(delta)?,
+        })
+    }
+}
+
 impl Add<usize> for Relocatable {
     type Output = Result<Relocatable, MathError>;
     fn add(self, other: usize) -> Result<Self, MathError> {
@@ -347,6 +361,13 @@ impl MaybeRelocatable {
         }
     }
 
+    /// Returns a reference to the inner value if it is a Felt252, or an `ExpectedInteger` error
+    /// pointing to `addr`, the address the value was read from, otherwise
+    pub fn try_int(&self, addr: Relocatable) -> Result<&Felt252, RunnerError> {
+        self.get_int_ref()
+            .ok_or_else(|| RunnerError::Memory(MemoryError::ExpectedInteger(Box::new(addr))))
+    }
+
     /// Returns the inner value if it is a Relocatable, returns None otherwise.
     pub fn get_relocatable(&self) -> Option<Relocatable> {
         match self {
@@ -678,6 +699,54 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn try_int() {
+        let addr = relocatable!(3, 4);
+        assert_eq!(mayberelocatable!(17).try_int(addr), Ok(&Felt252::from(17)));
+        assert_eq!(
+            mayberelocatable!(1, 2).try_int(addr),
+            Err(RunnerError::Memory(MemoryError::ExpectedInteger(Box::new(
+                addr
+            ))))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn offset_by_checked() {
+        let addr = relocatable!(2, 5);
+        assert_eq!(addr.offset_by_checked(3), Some(relocatable!(2, 8)));
+        assert_eq!(addr.offset_by_checked(-5), Some(relocatable!(2, 0)));
+        assert_eq!(addr.offset_by_checked(0), Some(addr));
+        assert_eq!(addr.offset_by_checked(-6), None);
+        assert_eq!(relocatable!(2, usize::MAX).offset_by_checked(1), None);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn sub_int_from_relocatable_neg_offset_error() {
+        assert_eq!(
+            MaybeRelocatable::from((7, 3)).sub(&MaybeRelocatable::from(Felt252::from(5_i32))),
+            Err(MathError::RelocatableSubFelt252NegOffset(Box::new((
+                Relocatable::from((7, 3)),
+                Felt252::from(5_i32)
+            ))))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn add_relocatable_to_int_offset_exceeded_error() {
+        assert_eq!(
+            mayberelocatable!(usize::MAX as i128 + 1).add(&mayberelocatable!(7, 0)),
+            Err(MathError::RelocatableAddFelt252OffsetExceeded(Box::new((
+                relocatable!(7, 0),
+                Felt252::from(usize::MAX) + 1_u64
+            ))))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn divmod_working() {
@@ -462,6 +462,97 @@ pub mod test_utils {
         }
     }
 
+    /// Runs `program`, expecting it to fail, and asserts that the failure was raised at `expected_pc`.
+    #[track_caller]
+    pub(crate) fn assert_fails_at_pc(
+        program: &crate::types::program::Program,
+        cairo_run_config: &crate::cairo_run::CairoRunConfig,
+        hint_processor: &mut dyn crate::hint_processor::hint_processor_definition::HintProcessor,
+        expected_pc: crate::utils::Relocatable,
+    ) {
+        match crate::cairo_run::cairo_run_program(program, cairo_run_config, hint_processor) {
+            Err(crate::vm::errors::cairo_run_errors::CairoRunError::VmException(exception)) => {
+                assert_eq!(exception.pc, expected_pc, "{exception}")
+            }
+            Err(error) => panic!("Expected a VmException, got: {error}"),
+            Ok(_) => panic!("Expected the program to fail at pc {expected_pc}"),
+        }
+    }
+
+    /// Returns the params of a hint with the given code and references, without ap tracking.
+    pub(crate) fn hint_params(
+        code: &str,
+        reference_ids: crate::stdlib::collections::HashMap<crate::stdlib::string::String, usize>,
+    ) -> crate::serde::deserialize_program::HintParams {
+        crate::serde::deserialize_program::HintParams {
+            code: code.into(),
+            accessible_scopes: crate::stdlib::vec::Vec::new(),
+            flow_tracking_data: crate::serde::deserialize_program::FlowTrackingData {
+                ap_tracking: crate::serde::deserialize_program::ApTracking::new(),
+                reference_ids,
+            },
+        }
+    }
+
+    /// Returns a program counting down from 10, which runs `loop_hints` at the start of each of
+    /// its 10 iterations (pc 2):
+    /// ```text
+    /// [ap] = 10, ap++
+    /// loop:
+    ///   [ap] = [ap - 1] - 1, ap++
+    ///   jmp loop if [ap - 1] != 0
+    /// ret
+    /// ```
+    pub(crate) fn countdown_program(
+        builtins: crate::stdlib::vec::Vec<crate::types::builtin_name::BuiltinName>,
+        loop_hints: crate::stdlib::vec::Vec<crate::serde::deserialize_program::HintParams>,
+    ) -> Program {
+        let hints = if loop_hints.is_empty() {
+            BTreeMap::new()
+        } else {
+            BTreeMap::from([(2, loop_hints)])
+        };
+        program!(
+            builtins = builtins,
+            data = vec_data!(
+                (0x480680017fff8000_i64),
+                (10),
+                (0x482480017fff8000_i64),
+                ((
+                    "3618502788666131213697322783095070105623107215331596699973092056135872020480",
+                    10
+                )),
+                (0x20680017fff7fff_i64),
+                ((
+                    "3618502788666131213697322783095070105623107215331596699973092056135872020479",
+                    10
+                )),
+                (0x208b7fff7fff7ffe_i64)
+            ),
+            hints = hints,
+            main = Some(0),
+        )
+    }
+
+    /// Returns a proof mode program that ends in an infinite loop, as proof mode programs do:
+    /// ```text
+    /// [ap] = 10, ap++
+    /// jmp rel 0
+    /// ```
+    pub(crate) fn proof_mode_loop_program() -> Program {
+        program!(
+            data = vec_data!(
+                (5189976364521848832_i64),
+                (10),
+                (74168662805676031_i64),
+                (0)
+            ),
+            main = Some(0),
+            start = Some(0),
+            end = Some(2),
+        )
+    }
+
     macro_rules! exec_scopes_ref {
         () => {
             &mut crate::types::exec_scope::ExecutionScopes::new()
@@ -1041,4 +1132,21 @@ mod test {
             program!(builtins = vec![BuiltinName::range_check], main = Some(2),)
         )
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_fails_at_pc_assert_eq_mismatch() {
+        // [ap] = 5
+        // [ap] = 6 <- fails, as [ap] already holds 5
+        let program = program!(
+            data = vec_data!((0x400680017fff8000_i64), (5), (0x400680017fff8000_i64), (6)),
+            main = Some(0),
+        );
+        assert_fails_at_pc(
+            &program,
+            &crate::cairo_run::CairoRunConfig::default(),
+            &mut BuiltinHintProcessor::new_empty(),
+            Relocatable::from((0, 2)),
+        );
+    }
 }
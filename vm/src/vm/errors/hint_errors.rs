@@ -42,6 +42,8 @@ pub enum HintError {
     UnknownIdentifierInternal,
     #[error("Wrong identifier type")]
     WrongIdentifierTypeInternal,
+    #[error("Expected a reference of type felt, found {0}")]
+    TypeMismatch(Box<str>),
     #[error("Hint Error: {0}")]
     CustomHint(Box<str>),
     #[error("Missing constant: {0}")]
@@ -178,6 +180,8 @@ pub enum HintError {
     InvalidLenValue(Box<Felt252>),
     #[error("recover_y: {0} does not represent the x coordinate of a point on the curve.")]
     RecoverYPointNotOnCurve(Box<Felt252>),
+    #[error("get_point_from_x: x_cube + beta = {0} has no square root modulo SECP_P, there is no point on the curve with the given x.")]
+    SecpPointNotOnCurve(Box<BigInt>),
     #[error("Invalid value for {}. Got: {}. Expected: {}", (*.0).0, (*.0).1, (*.0).2)]
     InvalidValue(Box<(&'static str, Felt252, Felt252)>),
     #[error("Attempt to subtract with overflow: ids.m - 1")]
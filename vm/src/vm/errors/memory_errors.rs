@@ -21,10 +21,14 @@ pub enum MemoryError {
     InsufficientAllocatedCells(#[from] InsufficientAllocatedCellsError),
     #[error("Can't insert into segment #{}; memory only has {} segment", (*.0).0, (*.0).1)]
     UnallocatedSegment(Box<(usize, usize)>),
+    #[error("Cairo PIE memory references address {addr}, whose segment doesn't exist in the run")]
+    PieSegmentOutOfRange { addr: Relocatable },
     #[error("Memory addresses must be relocatable")]
     AddressNotRelocatable,
     #[error("Range-check validation failed, number {} is out of valid range [0, {}]", (*.0).0, (*.0).1)]
     RangeCheckNumOutOfBounds(Box<(Felt252, Felt252)>),
+    #[error("Segment arena validation failed, {} segments were allocated but the limit is {}", (*.0).0, (*.0).1)]
+    SegmentArenaLimitExceeded(Box<(Felt252, usize)>),
     #[error("Range-check validation failed, encountered non-int value at address {0}")]
     RangeCheckFoundNonInt(Box<Relocatable>),
     #[error("Inconsistent memory assignment at address {:?}. {:?} != {:?}", (*.0).0, (*.0).1, (*.0).2)]
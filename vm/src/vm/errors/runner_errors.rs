@@ -1,7 +1,7 @@
 // The `(*.0).0` syntax of thiserror falsely triggers this clippy warning
 #![allow(clippy::explicit_auto_deref)]
 
-use crate::stdlib::{collections::HashSet, prelude::*};
+use crate::stdlib::prelude::*;
 use crate::types::builtin_name::BuiltinName;
 use crate::types::layout_name::LayoutName;
 use thiserror_no_std::Error;
@@ -9,6 +9,7 @@ use thiserror_no_std::Error;
 use super::{memory_errors::MemoryError, trace_errors::TraceError};
 use crate::types::{errors::math_errors::MathError, relocatable::Relocatable};
 use crate::Felt252;
+use num_bigint::BigUint;
 
 #[derive(Debug, PartialEq, Error)]
 pub enum RunnerError {
@@ -24,14 +25,16 @@ pub enum RunnerError {
     NoAP,
     #[error("Found None FP during VM initialization")]
     NoFP,
+    #[error("Found None final PC, the entrypoint must be initialized before running")]
+    NoFinalPC,
     #[error("Memory validation failed during VM initialization: {0}")]
     MemoryValidationError(MemoryError),
     #[error("Memory loading failed during state initialization: {0}")]
     MemoryInitializationError(MemoryError),
     #[error("Failed to convert string to FieldElement")]
     FailedStringConversion,
-    #[error("EcOpBuiltin: m should be at most {0}")]
-    EcOpBuiltinScalarLimit(Box<Felt252>),
+    #[error("EcOpBuiltin: m should be less than {0}")]
+    EcOpBuiltinScalarLimit(Box<BigUint>),
     #[error("Given builtins are not in appropiate order")]
     DisorderedBuiltins,
     #[error("Expected integer at address {:?} to be smaller than 2^{}, Got {}", (*.0).0, (*.0).1, (*.0).2)]
@@ -40,14 +43,16 @@ pub enum RunnerError {
     EcOpSameXCoordinate(Box<str>),
     #[error("EcOpBuiltin: point {0:?} is not on the curve")]
     PointNotOnCurve(Box<(Felt252, Felt252)>),
-    #[error("Builtin(s) {:?} not present in layout {}", (*.0).0, (*.0).1)]
-    NoBuiltinForInstance(Box<(HashSet<BuiltinName>, LayoutName)>),
+    #[error("Builtins {:?} are not present in layout {}", (*.0).0, (*.0).1)]
+    BuiltinNotInLayout(Box<(Vec<BuiltinName>, LayoutName)>),
     #[error("end_run called twice.")]
     EndRunCalledTwice,
     #[error("end_run must be called before finalize_segments.")]
     FinalizeNoEndRun,
     #[error("end_run must be called before read_return_values.")]
     ReadReturnValuesNoEndRun,
+    #[error("finalize_segments must be called before get_public_memory.")]
+    PublicMemoryNoFinalizeSegments,
     #[error("Error while finalizing segments: {0}")]
     FinalizeSegements(MemoryError),
     #[error("finalize_segments called but proof_mode is not enabled")]
@@ -128,14 +133,20 @@ pub enum RunnerError {
     MissingBuiltinStopPtrNotZero(BuiltinName),
     #[error("The number of steps in the Cairo PIE's execution resources does not match the number of steps in the RunResources")]
     PieNStepsVsRunResourcesNStepsMismatch,
-    #[error("A Cairo PIE can not be ran in proof_mode")]
-    CairoPieProofMode,
+    #[error("A Cairo PIE can not be ran in proof_mode (requested layout: {layout}), rerun it without proof_mode")]
+    CairoPieProofMode { layout: LayoutName },
     #[error("{0}: Invalid additional data")]
     InvalidAdditionalData(BuiltinName),
     #[error("dynamic layout params is missing")]
     MissingDynamicLayoutParams,
     #[error("dynamic layout {0} ratio should be 0 when disabled")]
     BadDynamicLayoutBuiltinRatio(BuiltinName),
+    #[error("Entrypoint {} is out of the program data bounds ({} words)", (*.0).0, (*.0).1)]
+    EntrypointOutOfBounds(Box<(usize, usize)>),
+    #[error("Hint reference {} points to the missing reference id {}", (*.0).0, (*.0).1)]
+    MissingHintReference(Box<(String, usize)>),
+    #[error("No compiled data was provided for hint {0}")]
+    MissingCompiledHint(usize),
 }
 
 #[cfg(test)]
@@ -148,4 +159,15 @@ mod tests {
         let size = crate::stdlib::mem::size_of::<RunnerError>();
         assert!(size <= 32, "{size}")
     }
+
+    #[test]
+    fn cairo_pie_proof_mode_message() {
+        let error = RunnerError::CairoPieProofMode {
+            layout: LayoutName::starknet,
+        };
+        assert_eq!(
+            error.to_string(),
+            "A Cairo PIE can not be ran in proof_mode (requested layout: starknet), rerun it without proof_mode"
+        );
+    }
 }
@@ -1,6 +1,11 @@
+// The `(*.0).0` syntax of thiserror falsely triggers this clippy warning
+#![allow(clippy::explicit_auto_deref)]
+
+use crate::stdlib::prelude::*;
 use thiserror_no_std::Error;
 
 use crate::vm::errors::memory_errors::MemoryError;
+use crate::Felt252;
 
 #[derive(Debug, PartialEq, Error)]
 pub enum TraceError {
@@ -16,6 +21,26 @@ pub enum TraceError {
     TraceNotRelocated,
 }
 
+#[derive(Debug, PartialEq, Error)]
+pub enum ReplayError {
+    #[error("Program data doesn't match memory at address {}: expected {}, found {:?}", (*.0).0, (*.0).1, (*.0).2)]
+    ProgramMismatch(Box<(usize, Felt252, Option<Felt252>)>),
+    #[error("Step {}: unknown value for memory cell {}", (*.0).0, (*.0).1)]
+    UnknownMemoryCell(Box<(usize, usize)>),
+    #[error("Step {}: invalid instruction at pc {}", (*.0).0, (*.0).1)]
+    InvalidInstruction(Box<(usize, usize)>),
+    #[error("Step {}: {} is not a valid memory address", (*.0).0, (*.0).1)]
+    InvalidAddress(Box<(usize, Felt252)>),
+    #[error("Step {0}: res is unconstrained")]
+    UnconstrainedRes(usize),
+    #[error("Step {}: an ASSERT_EQ instruction failed: {} != {}", (*.0).0, (*.0).1, (*.0).2)]
+    DiffAssertValues(Box<(usize, Felt252, Felt252)>),
+    #[error("Step {}: call wrote {} for the {}, expected {}", (*.0).0, (*.0).3, (*.0).1, (*.0).2)]
+    CallMismatch(Box<(usize, &'static str, Felt252, Felt252)>),
+    #[error("Step {}: next {} should be {}, found {}", (*.0).0, (*.0).1, (*.0).2, (*.0).3)]
+    RegisterMismatch(Box<(usize, &'static str, Felt252, Felt252)>),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -26,4 +51,11 @@ mod tests {
         let size = crate::stdlib::mem::size_of::<TraceError>();
         assert!(size <= 24, "{size}")
     }
+
+    #[test]
+    // Test to catch possible enum size regressions
+    fn test_replay_error_size() {
+        let size = crate::stdlib::mem::size_of::<ReplayError>();
+        assert!(size <= 16, "{size}")
+    }
 }
@@ -90,6 +90,14 @@ pub enum VirtualMachineError {
     NoSignatureBuiltin,
     #[error("Expected {0} to be present")]
     NoModBuiltin(BuiltinName),
+    #[error("Expected {0} builtin to be present")]
+    NoBuiltin(BuiltinName),
+    #[error("ap decreased at step {step}: from {prev} to {curr}")]
+    ApDecreased {
+        step: usize,
+        prev: usize,
+        curr: usize,
+    },
     #[error("Div out of range: 0 < {} <= {}", (*.0).0, (*.0).1)]
     OutOfValidRange(Box<(Felt252, Felt252)>),
     #[error("Failed to compare {} and {}, cant compare a relocatable to an integer value", (*.0).0, (*.0).1)]
@@ -136,6 +144,8 @@ pub enum VirtualMachineError {
     RelocationNotFound(usize),
     #[error("{} batch size is not {}", (*.0).0, (*.0).1)]
     ModBuiltinBatchSize(Box<(BuiltinName, usize)>),
+    #[error("Couldn't convert felt {0} to isize")]
+    BigintToIsizeFail(Box<Felt252>),
 }
 
 #[cfg(test)]
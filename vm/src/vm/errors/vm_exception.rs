@@ -21,6 +21,8 @@ use super::vm_errors::VirtualMachineError;
 pub struct VmException {
     pub pc: Relocatable,
     pub inst_location: Option<Location>,
+    /// Index of the failing hint among the hints at pc, if the error was raised by a hint
+    pub hint_index: Option<usize>,
     pub inner_exc: VirtualMachineError,
     pub error_attr_value: Option<String>,
     pub traceback: Option<String>,
@@ -46,6 +48,7 @@ impl VmException {
             } else {
                 None
             },
+            hint_index,
             inner_exc: error,
             error_attr_value,
             traceback: get_traceback(runner),
@@ -306,6 +309,7 @@ mod test {
     use crate::types::program::Program;
     use crate::types::relocatable::Relocatable;
     use crate::utils::test_utils::*;
+    use crate::vm::errors::hint_errors::HintError;
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
@@ -338,6 +342,7 @@ mod test {
             VmException {
                 pc: x,
                 inst_location: Some(y),
+                hint_index: None,
                 inner_exc: VirtualMachineError::NoImm,
                 error_attr_value: None,
                 traceback: None,
@@ -345,6 +350,54 @@ mod test {
         )
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_vm_exception_from_hint_error() {
+        let pc: Relocatable = (0, 0).into();
+        let location = |line| Location {
+            end_line: line,
+            end_col: 2,
+            input_file: InputFile {
+                filename: String::from("Folder/file.cairo"),
+            },
+            parent_location: None,
+            start_line: line,
+            start_col: 1,
+        };
+        let instruction_location = InstructionLocation {
+            inst: location(1),
+            hints: vec![
+                HintLocation {
+                    location: location(2),
+                    n_prefix_newlines: 0,
+                },
+                HintLocation {
+                    location: location(3),
+                    n_prefix_newlines: 0,
+                },
+            ],
+        };
+        let program = program!(
+            instruction_locations = Some(HashMap::from([(pc.offset, instruction_location)])),
+        );
+        let runner = cairo_runner!(program);
+        let error = VirtualMachineError::Hint(Box::new((1, HintError::WrongHintData)));
+        let vm_excep = VmException::from_vm_error(&runner, error);
+        assert_eq!(vm_excep.pc, pc);
+        assert_eq!(vm_excep.inst_location, Some(location(3)));
+        assert_eq!(vm_excep.hint_index, Some(1));
+        assert_eq!(
+            vm_excep.to_string(),
+            format!(
+                "{}\n",
+                location(3).to_string_with_content(&format!(
+                    "Error at pc=0:0:\n{}",
+                    VirtualMachineError::Hint(Box::new((1, HintError::WrongHintData)))
+                ))
+            )
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn location_to_string_no_message() {
@@ -391,6 +444,7 @@ mod test {
         let vm_excep = VmException {
             pc: (0, 2).into(),
             inst_location: None,
+            hint_index: None,
             inner_exc: VirtualMachineError::FailedToComputeOperands(Box::new((
                 "op0".to_string(),
                 Relocatable::from((0, 4)),
@@ -416,6 +470,7 @@ mod test {
         let vm_excep = VmException {
             pc: (0, 2).into(),
             inst_location: None,
+            hint_index: None,
             inner_exc: VirtualMachineError::FailedToComputeOperands(Box::new((
                 "op0".to_string(),
                 Relocatable::from((0, 4)),
@@ -451,6 +506,7 @@ mod test {
         let vm_excep = VmException {
             pc: (0, 2).into(),
             inst_location: Some(location),
+            hint_index: None,
             inner_exc: VirtualMachineError::FailedToComputeOperands(Box::new((
                 "op0".to_string(),
                 Relocatable::from((0, 4)),
@@ -498,6 +554,7 @@ mod test {
         let vm_excep = VmException {
             pc: (0, 2).into(),
             inst_location: Some(location),
+            hint_index: None,
             inner_exc: VirtualMachineError::FailedToComputeOperands(Box::new((
                 "op0".to_string(),
                 Relocatable::from((0, 4)),
@@ -1151,6 +1208,7 @@ cairo_programs/bad_programs/uint256_sub_b_gt_256.cairo:10:2: (pc=0:12)
             VmException {
                 pc: x,
                 inst_location: None,
+                hint_index: None,
                 inner_exc: VirtualMachineError::NoImm,
                 error_attr_value: None,
                 traceback: None,
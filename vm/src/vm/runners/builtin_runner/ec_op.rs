@@ -1,15 +1,18 @@
 use crate::air_private_input::{PrivateInput, PrivateInputEcOp};
+use crate::math_utils::curve::{STARK_ALPHA, STARK_BETA};
 use crate::stdlib::prelude::*;
 use crate::stdlib::{cell::RefCell, collections::HashMap};
 use crate::types::instance_definitions::ec_op_instance_def::{
     CELLS_PER_EC_OP, INPUT_CELLS_PER_EC_OP, SCALAR_HEIGHT,
 };
 use crate::types::relocatable::{MaybeRelocatable, Relocatable};
+use crate::utils::CAIRO_PRIME;
 use crate::vm::errors::memory_errors::MemoryError;
 use crate::vm::errors::runner_errors::RunnerError;
 use crate::vm::vm_memory::memory::Memory;
 use crate::vm::vm_memory::memory_segments::MemorySegmentManager;
 use crate::Felt252;
+use num_bigint::BigUint;
 use num_integer::{div_ceil, Integer};
 use starknet_types_core::curve::ProjectivePoint;
 
@@ -19,6 +22,9 @@ pub struct EcOpBuiltinRunner {
     pub base: usize,
     pub(crate) stop_ptr: Option<usize>,
     pub(crate) included: bool,
+    scalar_limit: BigUint,
+    scalar_height: u32,
+    same_x_as_infinity: bool,
     cache: RefCell<HashMap<Relocatable, Felt252>>,
 }
 
@@ -29,9 +35,38 @@ impl EcOpBuiltinRunner {
             ratio,
             stop_ptr: None,
             included,
+            scalar_limit: CAIRO_PRIME.clone(),
+            scalar_height: SCALAR_HEIGHT,
+            same_x_as_infinity: false,
             cache: RefCell::new(HashMap::new()),
         }
     }
+
+    /// Sets the number of bits of the scalar m processed by the builtin, 256 by default
+    pub(crate) fn with_scalar_height(mut self, scalar_height: u32) -> Self {
+        self.scalar_height = scalar_height;
+        self
+    }
+
+    /// When enabled, an instance that would fail with `EcOpSameXCoordinate` yields the point at
+    /// infinity sentinel (0, 0) instead.
+    /// Only meant for exploratory runs: `cairo_run` enables it through `CairoRunConfig` only when
+    /// `secure_run` is off, as the result is not a valid output of the builtin AIR.
+    pub fn set_same_x_as_infinity(&mut self, same_x_as_infinity: bool) {
+        self.same_x_as_infinity = same_x_as_infinity;
+    }
+
+    /// Sets the exclusive upper bound of the scalar m, checked when deducing the instance's output.
+    /// Defaults to the field prime, which accepts every scalar.
+    pub fn set_scalar_limit(&mut self, scalar_limit: BigUint) {
+        self.scalar_limit = scalar_limit;
+    }
+
+    ///Returns True if the scalar m is under the limit defined by scalar_limit.
+    pub fn is_valid_scalar(&self, m: &Felt252) -> bool {
+        m.to_biguint() < self.scalar_limit
+    }
+
     ///Returns True if the point (x, y) is on the elliptic curve defined as
     ///y^2 = x^3 + alpha * x + beta (mod p)
     ///or False otherwise.
@@ -51,27 +86,33 @@ impl EcOpBuiltinRunner {
         m: &Felt252,
         height: u32,
     ) -> Result<(Felt252, Felt252), RunnerError> {
-        let slope = m.to_biguint();
-        let mut partial_sum_b = ProjectivePoint::from_affine(partial_sum.0, partial_sum.1)
-            .map_err(|_| RunnerError::PointNotOnCurve(Box::new(partial_sum)))?;
-        let mut doubled_point_b = ProjectivePoint::from_affine(doubled_point.0, doubled_point.1)
-            .map_err(|_| RunnerError::PointNotOnCurve(Box::new(doubled_point)))?;
-        for i in 0..(height as u64).min(slope.bits()) {
-            if partial_sum_b.x() * doubled_point_b.z() == partial_sum_b.z() * doubled_point_b.x() {
-                return Err(RunnerError::EcOpSameXCoordinate(
-                    Self::format_ec_op_error(partial_sum_b, slope, doubled_point_b)
-                        .into_boxed_str(),
-                ));
-            };
-            if slope.bit(i) {
-                partial_sum_b += &doubled_point_b;
-            }
-            doubled_point_b = doubled_point_b.double();
-        }
-        partial_sum_b
-            .to_affine()
-            .map(|p| (p.x(), p.y()))
-            .map_err(|_| RunnerError::InvalidPoint)
+        let steps = EcOpSteps::new(partial_sum, doubled_point, m, height)?;
+        let initial_sum = steps.partial_sum.clone();
+        // Once the remaining bits of m are zero, the partial sum no longer changes
+        let n_bits = steps.m.bits() as usize;
+        let partial_sum_b = steps.take(n_bits).try_fold(initial_sum, |_, step| {
+            step.map(|(partial_sum, _)| partial_sum)
+        })?;
+        to_affine(&partial_sum_b)
+    }
+
+    /// Performs the same computation as `ec_op_impl`, recording the `(partial_sum, doubled_point)`
+    /// pair after each of the `height` iterations, so that intermediate results can be compared
+    /// against other implementations. The last recorded partial sum is the result of `ec_op_impl`.
+    #[cfg(feature = "test_utils")]
+    #[allow(clippy::type_complexity)]
+    pub fn ec_op_trace(
+        partial_sum: (Felt252, Felt252),
+        doubled_point: (Felt252, Felt252),
+        m: &Felt252,
+        height: u32,
+    ) -> Result<Vec<((Felt252, Felt252), (Felt252, Felt252))>, RunnerError> {
+        EcOpSteps::new(partial_sum, doubled_point, m, height)?
+            .map(|step| {
+                let (partial_sum, doubled_point) = step?;
+                Ok((to_affine(&partial_sum)?, to_affine(&doubled_point)?))
+            })
+            .collect()
     }
 
     pub fn initialize_segments(&mut self, segments: &mut MemorySegmentManager) {
@@ -102,10 +143,6 @@ impl EcOpBuiltinRunner {
         //Constant values declared here
         const EC_POINT_INDICES: [(usize, usize); 3] = [(0, 1), (2, 3), (5, 6)];
         const OUTPUT_INDICES: (usize, usize) = EC_POINT_INDICES[2];
-        let alpha: Felt252 = Felt252::ONE;
-        let beta_low: Felt252 = Felt252::from(0x609ad26c15c915c1f4cdfcb99cee9e89_u128);
-        let beta_high: Felt252 = Felt252::from(0x6f21413efbe40de150e596d72f7a8c5_u128);
-        let beta: Felt252 = (beta_high * (Felt252::ONE + Felt252::from(u128::MAX))) + beta_low;
 
         let index = address.offset.mod_floor(&(CELLS_PER_EC_OP as usize));
         //Index should be an output cell
@@ -124,34 +161,26 @@ impl EcOpBuiltinRunner {
         //If an input cell is not filled, return None
         let mut input_cells = Vec::<Felt252>::with_capacity(INPUT_CELLS_PER_EC_OP as usize);
         for i in 0..INPUT_CELLS_PER_EC_OP as usize {
-            match memory.get(&(instance + i)?) {
+            let addr = (instance + i)?;
+            match memory.get(&addr) {
                 None => return Ok(None),
-                Some(addr) => {
-                    input_cells.push(match addr.as_ref() {
-                        MaybeRelocatable::Int(num) => *num,
-                        _ => {
-                            return Err(RunnerError::Memory(MemoryError::ExpectedInteger(
-                                Box::new((instance + i)?),
-                            )))
-                        }
-                    });
-                }
+                Some(value) => input_cells.push(*value.try_int(addr)?),
             };
         }
         //Assert that m is under the limit defined by scalar_limit.
-        /*if input_cells[M_INDEX].as_ref() >= &self.ec_op_builtin.scalar_limit {
-            return Err(RunnerError::EcOpBuiltinScalarLimit(
-                self.ec_op_builtin.scalar_limit.clone(),
-            ));
-        }*/
+        if !self.is_valid_scalar(&input_cells[4]) {
+            return Err(RunnerError::EcOpBuiltinScalarLimit(Box::new(
+                self.scalar_limit.clone(),
+            )));
+        }
 
         // Assert that if the current address is part of a point, the point is on the curve
         for pair in &EC_POINT_INDICES[0..2] {
             if !EcOpBuiltinRunner::point_on_curve(
                 &input_cells[pair.0],
                 &input_cells[pair.1],
-                &alpha,
-                &beta,
+                &STARK_ALPHA,
+                &STARK_BETA,
             ) {
                 return Err(RunnerError::PointNotOnCurve(Box::new((
                     input_cells[pair.0],
@@ -159,12 +188,17 @@ impl EcOpBuiltinRunner {
                 ))));
             };
         }
-        let result = EcOpBuiltinRunner::ec_op_impl(
+        let result = match EcOpBuiltinRunner::ec_op_impl(
             (input_cells[0].to_owned(), input_cells[1].to_owned()),
             (input_cells[2].to_owned(), input_cells[3].to_owned()),
             &input_cells[4],
-            SCALAR_HEIGHT,
-        )?;
+            self.scalar_height,
+        ) {
+            Err(RunnerError::EcOpSameXCoordinate(_)) if self.same_x_as_infinity => {
+                (Felt252::ZERO, Felt252::ZERO)
+            }
+            result => result?,
+        };
         self.cache.borrow_mut().insert(x_addr, result.0);
         self.cache.borrow_mut().insert(
             (x_addr + 1usize)
@@ -192,6 +226,37 @@ impl EcOpBuiltinRunner {
         Ok(div_ceil(used_cells, CELLS_PER_EC_OP as usize))
     }
 
+    /// Counts the instances used by scanning the builtin segment for its highest written cell.
+    /// Unlike `get_used_instances`, this doesn't require the segment sizes to be computed.
+    pub fn count_instances(&self, memory: &Memory) -> usize {
+        memory
+            .data
+            .get(self.base)
+            .and_then(|segment| segment.iter().rposition(|cell| cell.is_some()))
+            .map(|highest| div_ceil(highest + 1, CELLS_PER_EC_OP as usize))
+            .unwrap_or_default()
+    }
+
+    /// Deduces the output cells of every instance in the builtin segment whose input cells are all
+    /// filled, returning the `(x, y)` outputs with their addresses.
+    /// Instances with missing input cells are skipped.
+    pub fn deduce_all_outputs(
+        &self,
+        memory: &Memory,
+    ) -> Result<Vec<(Relocatable, MaybeRelocatable)>, RunnerError> {
+        let mut outputs = Vec::new();
+        for instance in 0..self.count_instances(memory) {
+            let x_offset = instance * CELLS_PER_EC_OP as usize + INPUT_CELLS_PER_EC_OP as usize;
+            for offset in [x_offset, x_offset + 1] {
+                let address = Relocatable::from((self.base as isize, offset));
+                if let Some(value) = self.deduce_memory_cell(address, memory)? {
+                    outputs.push((address, value));
+                }
+            }
+        }
+        Ok(outputs)
+    }
+
     pub fn format_ec_op_error(
         p: ProjectivePoint,
         m: num_bigint::BigUint,
@@ -237,17 +302,96 @@ impl EcOpBuiltinRunner {
     }
 }
 
+fn to_affine(point: &ProjectivePoint) -> Result<(Felt252, Felt252), RunnerError> {
+    point
+        .to_affine()
+        .map(|p| (p.x(), p.y()))
+        .map_err(|_| RunnerError::InvalidPoint)
+}
+
+/// Iterates over the `height` steps of the ec_op computation, yielding the
+/// `(partial_sum, doubled_point)` pair after each step. Stops after yielding an
+/// `EcOpSameXCoordinate` error.
+struct EcOpSteps {
+    partial_sum: ProjectivePoint,
+    doubled_point: ProjectivePoint,
+    m: BigUint,
+    step: u64,
+    height: u64,
+}
+
+impl EcOpSteps {
+    /// Fails if either point is not on the curve, or with `EcOpBuiltinScalarLimit` if m doesn't
+    /// fit in `height` bits, as the builtin AIR requires m to be zero after processing `height` bits.
+    fn new(
+        partial_sum: (Felt252, Felt252),
+        doubled_point: (Felt252, Felt252),
+        m: &Felt252,
+        height: u32,
+    ) -> Result<Self, RunnerError> {
+        let m = m.to_biguint();
+        if m.bits() > height as u64 {
+            return Err(RunnerError::EcOpBuiltinScalarLimit(Box::new(
+                BigUint::from(1_u32) << height,
+            )));
+        }
+        Ok(EcOpSteps {
+            partial_sum: ProjectivePoint::from_affine(partial_sum.0, partial_sum.1)
+                .map_err(|_| RunnerError::PointNotOnCurve(Box::new(partial_sum)))?,
+            doubled_point: ProjectivePoint::from_affine(doubled_point.0, doubled_point.1)
+                .map_err(|_| RunnerError::PointNotOnCurve(Box::new(doubled_point)))?,
+            m,
+            step: 0,
+            height: height as u64,
+        })
+    }
+}
+
+impl Iterator for EcOpSteps {
+    type Item = Result<(ProjectivePoint, ProjectivePoint), RunnerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step >= self.height {
+            return None;
+        }
+        // Once the remaining bits of m are zero, the partial sum no longer changes
+        if self.step < self.m.bits() {
+            if self.partial_sum.x() * self.doubled_point.z()
+                == self.partial_sum.z() * self.doubled_point.x()
+            {
+                self.step = self.height;
+                return Some(Err(RunnerError::EcOpSameXCoordinate(
+                    EcOpBuiltinRunner::format_ec_op_error(
+                        self.partial_sum.clone(),
+                        self.m.clone(),
+                        self.doubled_point.clone(),
+                    )
+                    .into_boxed_str(),
+                )));
+            };
+            if self.m.bit(self.step) {
+                self.partial_sum += &self.doubled_point;
+            }
+        }
+        self.doubled_point = self.doubled_point.double();
+        self.step += 1;
+        Some(Ok((self.partial_sum.clone(), self.doubled_point.clone())))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
     use crate::types::builtin_name::BuiltinName;
+    use crate::types::instance_definitions::ec_op_instance_def::EcOpInstanceDef;
     use crate::types::layout_name::LayoutName;
     use crate::types::program::Program;
     use crate::utils::test_utils::*;
     use crate::vm::errors::cairo_run_errors::CairoRunError;
     use crate::vm::errors::vm_errors::VirtualMachineError;
     use crate::{felt_hex, felt_str, relocatable};
+    use assert_matches::assert_matches;
 
     use crate::vm::{
         errors::{memory_errors::MemoryError, runner_errors::RunnerError},
@@ -269,6 +413,43 @@ mod tests {
         assert_eq!(builtin.get_used_instances(&vm.segments), Ok(1));
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn count_instances_two_complete_instances() {
+        let builtin = EcOpBuiltinRunner::new(Some(10), true);
+
+        let mut vm = vm!();
+        vm.segments = segments![
+            ((0, 0), 1),
+            ((0, 1), 2),
+            ((0, 2), 3),
+            ((0, 3), 4),
+            ((0, 4), 5),
+            ((0, 5), 6),
+            ((0, 6), 7),
+            ((0, 7), 8),
+            ((0, 8), 9),
+            ((0, 9), 10),
+            ((0, 10), 11),
+            ((0, 11), 12),
+            ((0, 12), 13),
+            ((0, 13), 14)
+        ];
+
+        assert_eq!(builtin.count_instances(&vm.segments.memory), 2);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn is_valid_scalar() {
+        let mut builtin = EcOpBuiltinRunner::new(Some(10), true);
+        assert!(builtin.is_valid_scalar(&Felt252::MAX));
+
+        builtin.set_scalar_limit(BigUint::from(100_u32));
+        assert!(builtin.is_valid_scalar(&Felt252::from(99)));
+        assert!(!builtin.is_valid_scalar(&Felt252::from(100)));
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn final_stack() {
@@ -369,6 +550,63 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_used_cells_and_allocated_size_instance_counts() {
+        use crate::vm::errors::memory_errors::InsufficientAllocatedCellsError;
+        use crate::vm::runners::builtin_runner::used_cells_and_allocated_size;
+
+        let builtin: BuiltinRunner = EcOpBuiltinRunner::new(Some(10), true).into();
+        let mut vm = vm!();
+        vm.current_step = 100;
+
+        // 100 steps with a ratio of 10 allocate 10 instances of 7 cells each
+        vm.segments.segment_used_sizes = Some(vec![7]);
+        assert_eq!(builtin.get_allocated_instances(&vm), Ok(10));
+        assert_eq!(builtin.get_used_cells_and_allocated_size(&vm), Ok((7, 70)));
+
+        vm.segments.segment_used_sizes = Some(vec![70]);
+        assert_eq!(builtin.get_used_cells_and_allocated_size(&vm), Ok((70, 70)));
+
+        vm.segments.segment_used_sizes = Some(vec![77]);
+        assert_eq!(
+            builtin.get_used_cells_and_allocated_size(&vm),
+            Err(MemoryError::InsufficientAllocatedCells(
+                InsufficientAllocatedCellsError::BuiltinCells(Box::new((
+                    BuiltinName::ec_op,
+                    77,
+                    70
+                )))
+            ))
+        );
+
+        vm.segments.segment_used_sizes = Some(vec![0]);
+        vm.current_step = 5;
+        assert_eq!(
+            builtin.get_used_cells_and_allocated_size(&vm),
+            Err(MemoryError::InsufficientAllocatedCells(
+                InsufficientAllocatedCellsError::MinStepNotReached(Box::new((
+                    10,
+                    BuiltinName::ec_op
+                )))
+            ))
+        );
+
+        // The dynamic layout rounds the 3 used instances up to 4
+        assert_eq!(
+            used_cells_and_allocated_size(
+                BuiltinName::ec_op,
+                CELLS_PER_EC_OP,
+                None,
+                None,
+                1,
+                21,
+                0
+            ),
+            Ok((21, 28))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_used_cells_and_allocated_size_test() {
@@ -415,6 +653,21 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn max_instances_for_steps() {
+        let instance_def = EcOpInstanceDef::new(Some(10), None);
+        let builtin: BuiltinRunner = EcOpBuiltinRunner::new(instance_def.ratio, true).into();
+        assert_eq!(builtin.max_instances_for_steps(0), Some(0));
+        assert_eq!(builtin.max_instances_for_steps(9), Some(0));
+        assert_eq!(builtin.max_instances_for_steps(10), Some(1));
+        assert_eq!(builtin.max_instances_for_steps(1000), Some(100));
+        assert_eq!(builtin.max_instances_for_steps(1024), Some(102));
+
+        let builtin: BuiltinRunner = EcOpBuiltinRunner::new(None, true).into();
+        assert_eq!(builtin.max_instances_for_steps(1024), None);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_allocated_memory_units() {
@@ -462,9 +715,12 @@ mod tests {
     fn point_is_on_curve_a() {
         let x = felt_hex!("0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca");
         let y = felt_hex!("0x5668060aa49730b7be4801df46ec62de53ecd11abe43a32873000c36e8dc1f");
-        let alpha = Felt252::ONE;
-        let beta = felt_hex!("0x6f21413efbe40de150e596d72f7a8c5609ad26c15c915c1f4cdfcb99cee9e89");
-        assert!(EcOpBuiltinRunner::point_on_curve(&x, &y, &alpha, &beta));
+        assert!(EcOpBuiltinRunner::point_on_curve(
+            &x,
+            &y,
+            &STARK_ALPHA,
+            &STARK_BETA
+        ));
     }
 
     #[test]
@@ -472,9 +728,12 @@ mod tests {
     fn point_is_on_curve_b() {
         let x = felt_hex!("0x6f0a1ddaf19c44781c8946db396f494a10ffab183c2d8cf6c4cd321a8d87fd9");
         let y = felt_hex!("0x4afa52a9ef8c023d3385fddb6e1d78d57b0693b9b02d45d0f939b526d474c39");
-        let alpha = Felt252::ONE;
-        let beta = felt_hex!("0x6f21413efbe40de150e596d72f7a8c5609ad26c15c915c1f4cdfcb99cee9e89");
-        assert!(EcOpBuiltinRunner::point_on_curve(&x, &y, &alpha, &beta));
+        assert!(EcOpBuiltinRunner::point_on_curve(
+            &x,
+            &y,
+            &STARK_ALPHA,
+            &STARK_BETA
+        ));
     }
 
     #[test]
@@ -482,9 +741,12 @@ mod tests {
     fn point_is_not_on_curve_a() {
         let x = felt_hex!("0x1ef15c1a2162fb0d2e5d83196a6fb0509632fab5d746f0c3d723d8bc943cfca");
         let y = felt_hex!("0x5668060aa49730b7be4801df46ec62de53ecd11abe43a32873000c36e8dc1f");
-        let alpha = Felt252::ONE;
-        let beta = felt_hex!("0x6f21413efbe40de150e596d72f7a8c5609ad26c15c915c1f4cdfcb99cee9e89");
-        assert!(!EcOpBuiltinRunner::point_on_curve(&x, &y, &alpha, &beta));
+        assert!(!EcOpBuiltinRunner::point_on_curve(
+            &x,
+            &y,
+            &STARK_ALPHA,
+            &STARK_BETA
+        ));
     }
 
     #[test]
@@ -492,9 +754,12 @@ mod tests {
     fn point_is_not_on_curve_b() {
         let x = felt_hex!("0x6f0a1ddaeb88837dcc8ac9a48f894deed706bc3e8998e63535e2c91a8d87fd9");
         let y = felt_hex!("0x4afa52a9ef8c023d33ea3865fb4e0e49abfc50dd50ccea867539b526d474c39");
-        let alpha = Felt252::ONE;
-        let beta = felt_hex!("0x6f21413efbe40de150e596d72f7a8c5609ad26c15c915c1f4cdfcb99cee9e89");
-        assert!(!EcOpBuiltinRunner::point_on_curve(&x, &y, &alpha, &beta));
+        assert!(!EcOpBuiltinRunner::point_on_curve(
+            &x,
+            &y,
+            &STARK_ALPHA,
+            &STARK_BETA
+        ));
     }
 
     #[test]
@@ -524,6 +789,33 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "test_utils")]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn ec_op_trace_matches_ec_op_impl() {
+        let partial_sum = (
+            felt_hex!("0x6f0a1ddaf19c44781c8946db396f494a10ffab183c2d8cf6c4cd321a8d87fd9"),
+            felt_hex!("0x4afa52a9ef8c023d3385fddb6e1d78d57b0693b9b02d45d0f939b526d474c39"),
+        );
+        let doubled_point = (
+            felt_hex!("0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca"),
+            felt_hex!("0x5668060aa49730b7be4801df46ec62de53ecd11abe43a32873000c36e8dc1f"),
+        );
+        let m = Felt252::from(34);
+        let height = 256;
+        let trace = EcOpBuiltinRunner::ec_op_trace(partial_sum, doubled_point, &m, height).unwrap();
+        assert_eq!(trace.len(), height as usize);
+        // m = 0b100010, so the partial sum only changes on the second and sixth iterations
+        assert_eq!(trace[0].0, partial_sum);
+        assert_ne!(trace[1].0, trace[0].0);
+        assert_eq!(trace[4].0, trace[1].0);
+        assert_ne!(trace[5].0, trace[4].0);
+        assert_eq!(
+            Ok(trace.last().unwrap().0),
+            EcOpBuiltinRunner::ec_op_impl(partial_sum, doubled_point, &m, height)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn compute_ec_op_impl_valid_b() {
@@ -646,6 +938,223 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deduce_memory_cell_ec_op_scalar_over_limit() {
+        let memory = memory![
+            (
+                (3, 0),
+                (
+                    "0x68caa9509b7c2e90b4d92661cbf7c465471c1e8598c5f989691eef6653e0f38",
+                    16
+                )
+            ),
+            (
+                (3, 1),
+                (
+                    "0x79a8673f498531002fc549e06ff2010ffc0c191cceb7da5532acb95cdcb591",
+                    16
+                )
+            ),
+            (
+                (3, 2),
+                (
+                    "0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca",
+                    16
+                )
+            ),
+            (
+                (3, 3),
+                (
+                    "0x5668060aa49730b7be4801df46ec62de53ecd11abe43a32873000c36e8dc1f",
+                    16
+                )
+            ),
+            ((3, 4), 34)
+        ];
+        let mut builtin = EcOpBuiltinRunner::new(Some(256), true);
+        builtin.set_scalar_limit(BigUint::from(35_u32));
+        assert_matches!(
+            builtin.deduce_memory_cell(Relocatable::from((3, 5)), &memory),
+            Ok(Some(_))
+        );
+
+        let mut builtin = EcOpBuiltinRunner::new(Some(256), true);
+        builtin.set_scalar_limit(BigUint::from(34_u32));
+        assert_eq!(
+            builtin.deduce_memory_cell(Relocatable::from((3, 5)), &memory),
+            Err(RunnerError::EcOpBuiltinScalarLimit(Box::new(
+                BigUint::from(34_u32)
+            )))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deduce_memory_cell_ec_op_same_x_as_infinity() {
+        let p = (
+            felt_hex!("0x6f0a1ddaf19c44781c8946db396f494a10ffab183c2d8cf6c4cd321a8d87fd9"),
+            felt_hex!("0x4afa52a9ef8c023d3385fddb6e1d78d57b0693b9b02d45d0f939b526d474c39"),
+        );
+        let mut memory = Memory::new();
+        for _ in 0..4 {
+            memory.data.push(Vec::new());
+        }
+        for (offset, value) in [p.0, p.1, p.0, p.1, Felt252::from(34)]
+            .into_iter()
+            .enumerate()
+        {
+            memory
+                .insert(
+                    Relocatable::from((3, offset)),
+                    &MaybeRelocatable::from(value),
+                )
+                .unwrap();
+        }
+
+        let mut builtin = EcOpBuiltinRunner::new(Some(256), true);
+        assert_matches!(
+            builtin.deduce_memory_cell(Relocatable::from((3, 6)), &memory),
+            Err(RunnerError::EcOpSameXCoordinate(_))
+        );
+
+        builtin.set_same_x_as_infinity(true);
+        assert_eq!(
+            builtin.deduce_memory_cell(Relocatable::from((3, 5)), &memory),
+            Ok(Some(MaybeRelocatable::from(Felt252::ZERO)))
+        );
+        assert_eq!(
+            builtin.deduce_memory_cell(Relocatable::from((3, 6)), &memory),
+            Ok(Some(MaybeRelocatable::from(Felt252::ZERO)))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn missing_input_cells() {
+        let builtin: BuiltinRunner = EcOpBuiltinRunner::new(Some(256), true).into();
+        let memory = memory![
+            // Complete instance
+            ((0, 0), 1),
+            ((0, 1), 2),
+            ((0, 2), 3),
+            ((0, 3), 4),
+            ((0, 4), 5),
+            // Instance missing its 4th input cell
+            ((0, 7), 1),
+            ((0, 8), 2),
+            ((0, 9), 3),
+            ((0, 11), 5)
+        ];
+        assert_eq!(
+            builtin.missing_input_cells(&memory),
+            vec![Relocatable::from((0, 10))]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deduce_all_outputs_skips_partial_instances() {
+        let p = (
+            felt_hex!("0x68caa9509b7c2e90b4d92661cbf7c465471c1e8598c5f989691eef6653e0f38"),
+            felt_hex!("0x79a8673f498531002fc549e06ff2010ffc0c191cceb7da5532acb95cdcb591"),
+        );
+        let q = (
+            felt_hex!("0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca"),
+            felt_hex!("0x5668060aa49730b7be4801df46ec62de53ecd11abe43a32873000c36e8dc1f"),
+        );
+        let mut memory = Memory::new();
+        memory.data.push(Vec::new());
+        // Two filled instances, with m = 34 and m = 1, followed by one missing m
+        let inputs = [
+            (0, vec![p.0, p.1, q.0, q.1, Felt252::from(34)]),
+            (7, vec![p.0, p.1, q.0, q.1, Felt252::ONE]),
+            (14, vec![p.0, p.1, q.0, q.1]),
+        ];
+        for (base, cells) in inputs {
+            for (i, value) in cells.into_iter().enumerate() {
+                memory
+                    .insert(
+                        Relocatable::from((0, base + i)),
+                        &MaybeRelocatable::from(value),
+                    )
+                    .unwrap();
+            }
+        }
+
+        let builtin = EcOpBuiltinRunner::new(Some(256), true);
+        let mut sum = ProjectivePoint::from_affine(p.0, p.1).unwrap();
+        sum += &ProjectivePoint::from_affine(q.0, q.1).unwrap();
+        let sum = sum.to_affine().unwrap();
+        assert_eq!(
+            builtin.deduce_all_outputs(&memory),
+            Ok(vec![
+                (
+                    Relocatable::from((0, 5)),
+                    MaybeRelocatable::from(felt_str!(
+                        "2778063437308421278851140253538604815869848682781135193774472480292420096757"
+                    ))
+                ),
+                (
+                    Relocatable::from((0, 6)),
+                    MaybeRelocatable::from(felt_str!(
+                        "3598390311618116577316045819420613574162151407434885460365915347732568210029"
+                    ))
+                ),
+                (Relocatable::from((0, 12)), MaybeRelocatable::from(sum.x())),
+                (Relocatable::from((0, 13)), MaybeRelocatable::from(sum.y())),
+            ])
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deduce_memory_cell_ec_op_with_scalar_height_8() {
+        let p = (
+            felt_hex!("0x68caa9509b7c2e90b4d92661cbf7c465471c1e8598c5f989691eef6653e0f38"),
+            felt_hex!("0x79a8673f498531002fc549e06ff2010ffc0c191cceb7da5532acb95cdcb591"),
+        );
+        let q = (
+            felt_hex!("0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca"),
+            felt_hex!("0x5668060aa49730b7be4801df46ec62de53ecd11abe43a32873000c36e8dc1f"),
+        );
+        let builtin = EcOpBuiltinRunner::new(Some(256), true).with_scalar_height(8);
+        let deduce_result = |m: u32| {
+            let mut memory = Memory::new();
+            for _ in 0..4 {
+                memory.data.push(Vec::new());
+            }
+            for (i, value) in [p.0, p.1, q.0, q.1, Felt252::from(m)].iter().enumerate() {
+                memory
+                    .insert(Relocatable::from((3, i)), &MaybeRelocatable::from(*value))
+                    .unwrap();
+            }
+            Ok((
+                builtin.deduce_memory_cell(Relocatable::from((3, 5)), &memory)?,
+                builtin.deduce_memory_cell(Relocatable::from((3, 6)), &memory)?,
+            ))
+        };
+
+        // Compute P + m * Q directly
+        let expected = (ProjectivePoint::from_affine(p.0, p.1).unwrap()
+            + &ProjectivePoint::from_affine(q.0, q.1).unwrap() * Felt252::from(34))
+        .to_affine()
+        .unwrap();
+        let expected = (
+            Some(MaybeRelocatable::from(expected.x())),
+            Some(MaybeRelocatable::from(expected.y())),
+        );
+        assert_eq!(deduce_result(34), Ok(expected));
+        // m must fit in 8 bits
+        builtin.cache.borrow_mut().clear();
+        assert_eq!(
+            deduce_result(256 + 34),
+            Err(RunnerError::EcOpBuiltinScalarLimit(Box::new(
+                BigUint::from(256_u32)
+            )))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn deduce_memory_cell_ec_op_for_preset_memory_unfilled_input_cells() {
@@ -21,6 +21,48 @@ lazy_static! {
     static ref KECCAK_INPUT_MAX: Felt252 = Felt252::TWO.pow(BITS);
 }
 
+/// Keccak-f[1600] permutation applied by the builtin, on a state of 25 little-endian lanes
+pub fn keccak_f(state: [u64; 25]) -> [u64; 25] {
+    let mut state = state;
+    keccak::f1600(&mut state);
+    state
+}
+
+/// Applies [keccak_f] to the input cells of a builtin instance, returning the values of its
+/// output cells. Each cell holds 200 bits of the state, in little-endian order. Only the low
+/// 200 bits of each input are used, the builtin rejects bigger inputs.
+pub fn keccak_f_felts(
+    input: &[Felt252; INPUT_CELLS_PER_KECCAK as usize],
+) -> [Felt252; INPUT_CELLS_PER_KECCAK as usize] {
+    let input_message: Vec<u8> = input
+        .iter()
+        .flat_map(|x| {
+            let mut bytes = x.to_bytes_le().to_vec();
+            bytes.resize(KECCAK_FELT_BYTE_SIZE, 0);
+            bytes
+        })
+        .collect();
+    let output_message = keccak_f_bytes(&input_message);
+    core::array::from_fn(|i| {
+        let mut bytes =
+            output_message[i * KECCAK_FELT_BYTE_SIZE..(i + 1) * KECCAK_FELT_BYTE_SIZE].to_vec();
+        bytes.resize(32, 0);
+        Felt252::from_bytes_le_slice(&bytes)
+    })
+}
+
+fn keccak_f_bytes(input_message: &[u8]) -> Vec<u8> {
+    let bigint = BigUint::from_bytes_le(input_message);
+    let mut keccak_input = bigint.to_u64_digits();
+    keccak_input.resize(25, 0);
+    // This unwrap wont fail as keccak_input's size is always 25
+    let keccak_input: [u64; 25] = keccak_input.try_into().unwrap();
+    keccak_f(keccak_input)
+        .iter()
+        .flat_map(|x| x.to_le_bytes())
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct KeccakBuiltinRunner {
     ratio: Option<u32>,
@@ -101,25 +143,13 @@ impl KeccakBuiltinRunner {
             };
             input_felts.push(val)
         }
-        let input_message: Vec<u8> = input_felts
-            .iter()
-            .flat_map(|x| {
-                let mut bytes = x.to_bytes_le().to_vec();
-                bytes.resize(KECCAK_FELT_BYTE_SIZE, 0);
-                bytes
-            })
-            .collect();
-        let keccak_result = Self::keccak_f(&input_message)?;
-
-        let mut start_index = 0_usize;
-        for i in 0..INPUT_CELLS_PER_KECCAK {
-            let end_index = start_index + BITS as usize / 8;
-            self.cache.borrow_mut().insert((first_output_addr + i)?, {
-                let mut bytes = keccak_result[start_index..end_index].to_vec();
-                bytes.resize(32, 0);
-                Felt252::from_bytes_le_slice(&bytes)
-            });
-            start_index = end_index;
+        // This unwrap wont fail as INPUT_CELLS_PER_KECCAK inputs were read
+        let input_felts: [Felt252; INPUT_CELLS_PER_KECCAK as usize] =
+            input_felts.try_into().unwrap();
+        for (i, output) in keccak_f_felts(&input_felts).into_iter().enumerate() {
+            self.cache
+                .borrow_mut()
+                .insert((first_output_addr + i)?, output);
         }
         Ok(self.cache.borrow().get(&address).map(|x| x.into()))
     }
@@ -152,16 +182,6 @@ impl KeccakBuiltinRunner {
         safe_div_usize(262144_usize, diluted_n_bits as usize).unwrap_or(0)
     }
 
-    fn keccak_f(input_message: &[u8]) -> Result<Vec<u8>, RunnerError> {
-        let bigint = BigUint::from_bytes_le(input_message);
-        let mut keccak_input = bigint.to_u64_digits();
-        keccak_input.resize(25, 0);
-        // This unwrap wont fail as keccak_input's size is always 25
-        let mut keccak_input: [u64; 25] = keccak_input.try_into().unwrap();
-        keccak::f1600(&mut keccak_input);
-        Ok(keccak_input.iter().flat_map(|x| x.to_le_bytes()).collect())
-    }
-
     pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
         let mut private_inputs = vec![];
         if let Some(segment) = memory.data.get(self.base) {
@@ -579,8 +599,73 @@ mod tests {
     fn keccak_f() {
         let input_bytes = b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x05\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x06\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x07\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x08\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00";
         let expected_output_bytes = b"\xf6\x98\x81\xe1\x00!\x1f.\xc4*\x8c\x0c\x7fF\xc8q8\xdf\xb9\xbe\x07H\xca7T1\xab\x16\x17\xa9\x11\xff-L\x87\xb2iY.\x96\x82x\xde\xbb\\up?uz:0\xee\x08\x1b\x15\xd6\n\xab\r\x0b\x87T:w\x0fH\xe7!f},\x08a\xe5\xbe8\x16\x13\x9a?\xad~<9\xf7\x03`\x8b\xd8\xa3F\x8aQ\xf9\n9\xcdD\xb7.X\xf7\x8e\x1f\x17\x9e \xe5i\x01rr\xdf\xaf\x99k\x9f\x8e\x84\\\xday`\xf1``\x02q+\x8e\xad\x96\xd8\xff\xff3<\xb6\x01o\xd7\xa6\x86\x9d\xea\xbc\xfb\x08\xe1\xa3\x1c\x06z\xab@\xa1\xc1\xb1xZ\x92\x96\xc0.\x01\x13g\x93\x87!\xa6\xa8z\x9c@\x0bY'\xe7\xa7Qr\xe5\xc1\xa3\xa6\x88H\xa5\xc0@9k:y\xd1Kw\xd5";
-        let output_bytes = KeccakBuiltinRunner::keccak_f(input_bytes);
-        assert_eq!(output_bytes, Ok(expected_output_bytes.to_vec()));
+        let output_bytes = keccak_f_bytes(input_bytes);
+        assert_eq!(output_bytes, expected_output_bytes.to_vec());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn keccak_f_known_answer() {
+        // Keccak-f[1600] applied to the all-zero state, from the Keccak team's reference values
+        let expected: [u64; 25] = [
+            0xF1258F7940E1DDE7,
+            0x84D5CCF933C0478A,
+            0xD598261EA65AA9EE,
+            0xBD1547306F80494D,
+            0x8B284E056253D057,
+            0xFF97A42D7F8E6FD4,
+            0x90FEE5A0A44647C4,
+            0x8C5BDA0CD6192E76,
+            0xAD30A6F71B19059C,
+            0x30935AB7D08FFC64,
+            0xEB5AA93F2317D635,
+            0xA9A6E6260D712103,
+            0x81A57C16DBCF555F,
+            0x43B831CD0347C826,
+            0x01F22F1A11A5569F,
+            0x05E5635A21D9AE61,
+            0x64BEFEF28CC970F2,
+            0x613670957BC46611,
+            0xB87C5A554FD00ECB,
+            0x8C3EE88A1CCF32C8,
+            0x940C7922AE3A2614,
+            0x1841F924A2C509E4,
+            0x16F53526E70465C2,
+            0x75F644E97F30A13B,
+            0xEAF1FF7B5CECA249,
+        ];
+        assert_eq!(super::keccak_f([0; 25]), expected);
+
+        // The felt wrapper packs the same state into the builtin's 8 cells of 200 bits
+        let state_bytes: Vec<u8> = expected.iter().flat_map(|x| x.to_le_bytes()).collect();
+        let expected_felts: Vec<Felt252> = state_bytes
+            .chunks(KECCAK_FELT_BYTE_SIZE)
+            .map(Felt252::from_bytes_le_slice)
+            .collect();
+        assert_eq!(keccak_f_felts(&[Felt252::ZERO; 8]).to_vec(), expected_felts);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn keccak_f_felts_matches_deduce_memory_cell() {
+        let builtin = KeccakBuiltinRunner::new(Some(2048), true);
+        let memory = memory![
+            ((0, 16), 43),
+            ((0, 17), 199),
+            ((0, 18), 0),
+            ((0, 19), 0),
+            ((0, 20), 0),
+            ((0, 21), 0),
+            ((0, 22), 0),
+            ((0, 23), 1)
+        ];
+        let input = [43, 199, 0, 0, 0, 0, 0, 1].map(Felt252::from);
+        for (i, output) in keccak_f_felts(&input).into_iter().enumerate() {
+            assert_eq!(
+                builtin.deduce_memory_cell(Relocatable::from((0, 24 + i)), &memory),
+                Ok(Some(output.into()))
+            );
+        }
     }
 
     #[test]
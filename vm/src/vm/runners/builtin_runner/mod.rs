@@ -56,6 +56,92 @@ pub use signature::SignatureBuiltinRunner;
 
 use super::cairo_pie::BuiltinAdditionalData;
 
+/// Returns the number of instances allocated to a builtin with the given layout parameters,
+/// after `current_step` steps and with `used_cells` cells already in use.
+///
+/// A `ratio` of `None` stands for the dynamic layout, where the builtin gets as many instances
+/// as it needs (rounded up to a power of two components).
+pub(crate) fn allocated_instances(
+    name: BuiltinName,
+    cells_per_instance: u32,
+    ratio: Option<u32>,
+    ratio_den: Option<u32>,
+    instances_per_component: u32,
+    used_cells: usize,
+    current_step: usize,
+) -> Result<usize, MemoryError> {
+    match ratio {
+        None => {
+            // Dynamic layout has the exact number of instances it needs (up to a power of 2).
+            let instances: usize = used_cells / cells_per_instance as usize;
+            let needed_components = instances / instances_per_component as usize;
+
+            let components = if needed_components > 0 {
+                needed_components.next_power_of_two()
+            } else {
+                0
+            };
+            Ok(instances_per_component as usize * components)
+        }
+        // Dynamic layout allows for builtins with ratio 0
+        Some(0) => Ok(0),
+        Some(ratio) => {
+            let min_step_num = (ratio * instances_per_component) as usize;
+            let min_step = if let Some(ratio_den) = ratio_den {
+                div_ceil(min_step_num, ratio_den as usize)
+            } else {
+                min_step_num
+            };
+
+            if current_step < min_step {
+                return Err(
+                    InsufficientAllocatedCellsError::MinStepNotReached(Box::new((min_step, name)))
+                        .into(),
+                );
+            };
+
+            let allocated_instances = if let Some(ratio_den) = ratio_den {
+                safe_div_usize(current_step * ratio_den as usize, ratio as usize)
+                    .map_err(|_| MemoryError::ErrorCalculatingMemoryUnits)?
+            } else {
+                safe_div_usize(current_step, ratio as usize)
+                    .map_err(|_| MemoryError::ErrorCalculatingMemoryUnits)?
+            };
+            Ok(allocated_instances)
+        }
+    }
+}
+
+/// Returns the used cells and the allocated size of a builtin with the given layout parameters,
+/// failing with [`InsufficientAllocatedCellsError::BuiltinCells`] if the builtin used more cells
+/// than were allocated to it.
+pub(crate) fn used_cells_and_allocated_size(
+    name: BuiltinName,
+    cells_per_instance: u32,
+    ratio: Option<u32>,
+    ratio_den: Option<u32>,
+    instances_per_component: u32,
+    used_cells: usize,
+    current_step: usize,
+) -> Result<(usize, usize), MemoryError> {
+    let size = allocated_instances(
+        name,
+        cells_per_instance,
+        ratio,
+        ratio_den,
+        instances_per_component,
+        used_cells,
+        current_step,
+    )? * cells_per_instance as usize;
+    if used_cells > size {
+        return Err(InsufficientAllocatedCellsError::BuiltinCells(Box::new((
+            name, used_cells, size,
+        )))
+        .into());
+    }
+    Ok((used_cells, size))
+}
+
 /* NB: this enum is no accident: we may need (and cairo-vm-py *does* need)
  * structs containing this to be `Send`. The only two ways to achieve that
  * are either storing a `dyn Trait` inside an `Arc<Mutex<&dyn Trait>>` or
@@ -182,47 +268,34 @@ impl BuiltinRunner {
         match *self {
             BuiltinRunner::Output(_) | BuiltinRunner::SegmentArena(_) => Ok(0),
             _ => {
-                match self.ratio() {
-                    None => {
-                        // Dynamic layout has the exact number of instances it needs (up to a power of 2).
-                        let instances: usize =
-                            self.get_used_cells(&vm.segments)? / self.cells_per_instance() as usize;
-                        let needed_components = instances / self.instances_per_component() as usize;
-
-                        let components = if needed_components > 0 {
-                            needed_components.next_power_of_two()
-                        } else {
-                            0
-                        };
-                        Ok(self.instances_per_component() as usize * components)
-                    }
-                    // Dynamic layout allows for builtins with ratio 0
-                    Some(0) => Ok(0),
-                    Some(ratio) => {
-                        let min_step_num = (ratio * self.instances_per_component()) as usize;
-                        let min_step = if let Some(ratio_den) = self.ratio_den() {
-                            div_ceil(min_step_num, ratio_den as usize)
-                        } else {
-                            min_step_num
-                        };
-
-                        if vm.current_step < min_step {
-                            return Err(InsufficientAllocatedCellsError::MinStepNotReached(
-                                Box::new((min_step, self.name())),
-                            )
-                            .into());
-                        };
-
-                        let allocated_instances = if let Some(ratio_den) = self.ratio_den() {
-                            safe_div_usize(vm.current_step * ratio_den as usize, ratio as usize)
-                                .map_err(|_| MemoryError::ErrorCalculatingMemoryUnits)?
-                        } else {
-                            safe_div_usize(vm.current_step, ratio as usize)
-                                .map_err(|_| MemoryError::ErrorCalculatingMemoryUnits)?
-                        };
-                        Ok(allocated_instances)
-                    }
-                }
+                // Only the dynamic layout depends on the used cells
+                let used_cells = match self.ratio() {
+                    None => self.get_used_cells(&vm.segments)?,
+                    Some(_) => 0,
+                };
+                allocated_instances(
+                    self.name(),
+                    self.cells_per_instance(),
+                    self.ratio(),
+                    self.ratio_den(),
+                    self.instances_per_component(),
+                    used_cells,
+                    vm.current_step,
+                )
+            }
+        }
+    }
+
+    /// Returns the number of instances that fit in a run of `n_steps` steps, given the builtin's
+    /// layout ratio, rounding down. Returns None for dynamic layout builtins, whose instances
+    /// depend on the cells they use rather than on the step count.
+    pub fn max_instances_for_steps(&self, n_steps: usize) -> Option<usize> {
+        match *self {
+            BuiltinRunner::Output(_) | BuiltinRunner::SegmentArena(_) => Some(0),
+            _ => {
+                let ratio = self.ratio()? as usize;
+                let ratio_den = self.ratio_den().unwrap_or(1) as usize;
+                Some(n_steps * ratio_den / ratio)
             }
         }
     }
@@ -292,6 +365,9 @@ impl BuiltinRunner {
             BuiltinRunner::RangeCheck96(ref range_check) => range_check.add_validation_rule(memory),
             BuiltinRunner::Signature(ref signature) => signature.add_validation_rule(memory),
             BuiltinRunner::Poseidon(ref poseidon) => poseidon.add_validation_rule(memory),
+            BuiltinRunner::SegmentArena(ref segment_arena) => {
+                segment_arena.add_validation_rule(memory)
+            }
             _ => {}
         }
     }
@@ -311,6 +387,33 @@ impl BuiltinRunner {
         }
     }
 
+    /// Returns the addresses of the input cells that haven't been written yet, for every instance
+    /// present in the builtin's segment. Outputs can't be deduced until all of them are filled.
+    /// The output and segment_arena builtins have no input cells and return an empty list.
+    pub fn missing_input_cells(&self, memory: &Memory) -> Vec<Relocatable> {
+        if matches!(
+            self,
+            BuiltinRunner::Output(_) | BuiltinRunner::SegmentArena(_)
+        ) {
+            return vec![];
+        }
+        let cells_per_instance = self.cells_per_instance() as usize;
+        let n_input_cells = self.n_input_cells() as usize;
+        let segment_index = self.base();
+        let n_instances = memory
+            .data
+            .get(segment_index)
+            .map_or(0, |segment| div_ceil(segment.len(), cells_per_instance));
+        (0..n_instances)
+            .flat_map(|instance| {
+                (0..n_input_cells).map(move |i| {
+                    Relocatable::from((segment_index as isize, instance * cells_per_instance + i))
+                })
+            })
+            .filter(|addr| memory.get(addr).is_none())
+            .collect()
+    }
+
     pub fn get_memory_segment_addresses(&self) -> (usize, Option<usize>) {
         (self.base(), self.stop_ptr())
     }
@@ -531,19 +634,15 @@ impl BuiltinRunner {
                 let used = self.get_used_cells(&vm.segments)?;
                 Ok((used, used))
             }
-            _ => {
-                let used = self.get_used_cells(&vm.segments)?;
-                let size = self.get_allocated_memory_units(vm)?;
-                if used > size {
-                    return Err(InsufficientAllocatedCellsError::BuiltinCells(Box::new((
-                        self.name(),
-                        used,
-                        size,
-                    )))
-                    .into());
-                }
-                Ok((used, size))
-            }
+            _ => used_cells_and_allocated_size(
+                self.name(),
+                self.cells_per_instance(),
+                self.ratio(),
+                self.ratio_den(),
+                self.instances_per_component(),
+                self.get_used_cells(&vm.segments)?,
+                vm.current_step,
+            ),
         }
     }
 
@@ -1728,4 +1827,48 @@ mod tests {
         let builtin: BuiltinRunner = PoseidonBuiltinRunner::new(None, true).into();
         assert_eq!(builtin.get_additional_data(), BuiltinAdditionalData::None)
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn collect_air_private_inputs_ec_op_and_range_check() {
+        let mut vm = vm!();
+        vm.builtin_runners = vec![
+            RangeCheckBuiltinRunner::<RC_N_PARTS_STANDARD>::new(Some(8), true).into(),
+            EcOpBuiltinRunner::new(Some(256), true).into(),
+            OutputBuiltinRunner::new(true).into(),
+        ];
+        for builtin in vm.builtin_runners.iter_mut() {
+            builtin.initialize_segments(&mut vm.segments);
+        }
+        vm.segments.memory = memory![
+            ((0, 0), 7),
+            ((0, 1), 9),
+            ((1, 0), 1),
+            ((1, 1), 2),
+            ((1, 2), 3),
+            ((1, 3), 4),
+            ((1, 4), 5),
+            ((2, 0), 11)
+        ];
+
+        let private_inputs: Vec<serde_json::Value> = vm
+            .builtin_runners
+            .iter()
+            .map(|builtin| serde_json::to_value(builtin.air_private_input(&vm.segments)).unwrap())
+            .collect();
+        assert_eq!(
+            private_inputs,
+            vec![
+                serde_json::json!([
+                    {"index": 0, "value": "0x7"},
+                    {"index": 1, "value": "0x9"}
+                ]),
+                serde_json::json!([
+                    {"index": 0, "p_x": "0x1", "p_y": "0x2", "q_x": "0x3", "q_y": "0x4", "m": "0x5"}
+                ]),
+                // Builtins without private input return an empty list
+                serde_json::json!([]),
+            ]
+        );
+    }
 }
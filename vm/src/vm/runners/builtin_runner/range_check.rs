@@ -11,7 +11,7 @@ use crate::Felt252;
 use crate::{
     types::relocatable::{MaybeRelocatable, Relocatable},
     vm::{
-        errors::memory_errors::MemoryError,
+        errors::{memory_errors::MemoryError, runner_errors::RunnerError},
         vm_memory::{
             memory::{Memory, ValidationRule},
             memory_segments::MemorySegmentManager,
@@ -111,19 +111,30 @@ impl<const N_PARTS: u64> RangeCheckBuiltinRunner<N_PARTS> {
                 let num = memory
                     .get_integer(address)
                     .map_err(|_| MemoryError::RangeCheckFoundNonInt(Box::new(address)))?;
-                if num.bits() as u64 <= N_PARTS * INNER_RC_BOUND_SHIFT {
-                    Ok(vec![address.to_owned()])
-                } else {
-                    Err(MemoryError::RangeCheckNumOutOfBounds(Box::new((
-                        num.into_owned(),
-                        Felt252::TWO.pow((N_PARTS * INNER_RC_BOUND_SHIFT) as u128),
-                    ))))
-                }
+                Self::check_value(&num)?;
+                Ok(vec![address.to_owned()])
             },
         ));
         memory.add_validation_rule(self.base, rule);
     }
 
+    /// Checks `value` against the bound enforced by the builtin's validation rule,
+    /// failing with `RangeCheckNumOutOfBounds` if `value >= 2^(16 * N_PARTS)`.
+    pub fn validate_value(&self, value: &Felt252) -> Result<(), RunnerError> {
+        Self::check_value(value).map_err(RunnerError::Memory)
+    }
+
+    fn check_value(num: &Felt252) -> Result<(), MemoryError> {
+        if num.bits() as u64 <= N_PARTS * INNER_RC_BOUND_SHIFT {
+            Ok(())
+        } else {
+            Err(MemoryError::RangeCheckNumOutOfBounds(Box::new((
+                *num,
+                Felt252::TWO.pow((N_PARTS * INNER_RC_BOUND_SHIFT) as u128),
+            ))))
+        }
+    }
+
     pub fn get_used_cells(&self, segments: &MemorySegmentManager) -> Result<usize, MemoryError> {
         segments
             .get_segment_used_size(self.base)
@@ -181,7 +192,6 @@ mod tests {
     use super::*;
     use crate::relocatable;
     use crate::types::builtin_name::BuiltinName;
-    use crate::vm::errors::runner_errors::RunnerError;
     use crate::vm::vm_memory::memory::Memory;
     use crate::{
         hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
@@ -202,6 +212,30 @@ mod tests {
         assert_eq!(builtin.get_used_instances(&vm.segments), Ok(1));
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_value() {
+        let builtin = RangeCheckBuiltinRunner::<RC_N_PARTS_STANDARD>::new(Some(10), true);
+        let bound = Felt252::TWO.pow(128_u32);
+        assert_eq!(builtin.validate_value(&(bound - Felt252::ONE)), Ok(()));
+        assert_eq!(
+            builtin.validate_value(&bound),
+            Err(RunnerError::Memory(MemoryError::RangeCheckNumOutOfBounds(
+                Box::new((bound, bound))
+            )))
+        );
+
+        let builtin = RangeCheckBuiltinRunner::<RC_N_PARTS_96>::new(Some(10), true);
+        let bound = Felt252::TWO.pow(96_u32);
+        assert_eq!(builtin.validate_value(&(bound - Felt252::ONE)), Ok(()));
+        assert_eq!(
+            builtin.validate_value(&bound),
+            Err(RunnerError::Memory(MemoryError::RangeCheckNumOutOfBounds(
+                Box::new((bound, bound))
+            )))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn final_stack() {
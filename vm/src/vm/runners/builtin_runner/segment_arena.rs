@@ -1,12 +1,17 @@
 use crate::vm::errors::memory_errors::MemoryError;
 use crate::{
     types::relocatable::{MaybeRelocatable, Relocatable},
-    vm::vm_memory::memory_segments::MemorySegmentManager,
+    vm::vm_memory::{
+        memory::{Memory, ValidationRule},
+        memory_segments::MemorySegmentManager,
+    },
+    Felt252,
 };
 
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 use num_integer::div_ceil;
+use num_traits::ToPrimitive;
 
 pub(crate) const ARENA_BUILTIN_SIZE: u32 = 3;
 // The size of the builtin segment at the time of its creation.
@@ -17,6 +22,7 @@ pub struct SegmentArenaBuiltinRunner {
     base: Relocatable,
     pub(crate) included: bool,
     pub(crate) stop_ptr: Option<usize>,
+    max_segments: Option<usize>,
 }
 
 impl SegmentArenaBuiltinRunner {
@@ -25,9 +31,62 @@ impl SegmentArenaBuiltinRunner {
             base: Relocatable::from((0, 0)),
             included,
             stop_ptr: None,
+            max_segments: None,
         }
     }
 
+    /// Sets the maximum number of segments that can be allocated in the arena, enforced by the
+    /// builtin's validation rule. There is no limit by default.
+    pub fn set_max_segments(&mut self, max_segments: Option<usize>) {
+        self.max_segments = max_segments;
+    }
+
+    /// Returns the number of segments allocated in the arena so far, as recorded in the
+    /// `n_segments` cell of its latest instance.
+    pub fn allocated_segments(&self, memory: &Memory) -> usize {
+        let segment_index = self.base.segment_index;
+        let n_instances = memory.data.get(self.base()).map_or(0, |segment| {
+            div_ceil(segment.len(), ARENA_BUILTIN_SIZE as usize)
+        });
+        (0..n_instances)
+            .rev()
+            .find_map(|instance| {
+                let n_segments_addr =
+                    Relocatable::from((segment_index, instance * ARENA_BUILTIN_SIZE as usize + 1));
+                memory.get_integer(n_segments_addr).ok()
+            })
+            .and_then(|n_segments| n_segments.to_usize())
+            .unwrap_or_default()
+    }
+
+    /// Adds a validation rule failing with [`MemoryError::SegmentArenaLimitExceeded`] as soon as an
+    /// instance records more allocated segments than allowed by
+    /// [`SegmentArenaBuiltinRunner::set_max_segments`]. No rule is added if there is no limit.
+    pub fn add_validation_rule(&self, memory: &mut Memory) {
+        let Some(max_segments) = self.max_segments else {
+            return;
+        };
+        let rule = ValidationRule(Box::new(
+            move |memory: &Memory, address: Relocatable| -> Result<Vec<Relocatable>, MemoryError> {
+                // Each instance is (infos, n_segments, n_finalized)
+                if address.offset % ARENA_BUILTIN_SIZE as usize != 1 {
+                    return Ok(vec![]);
+                }
+                let Ok(n_segments) = memory.get_integer(address) else {
+                    return Ok(vec![]);
+                };
+                if n_segments.as_ref() > &Felt252::from(max_segments) {
+                    return Err(MemoryError::SegmentArenaLimitExceeded(Box::new((
+                        *n_segments,
+                        max_segments,
+                    ))));
+                }
+                Ok(vec![address])
+            },
+        ));
+        memory.add_validation_rule(self.base.segment_index as usize, rule);
+    }
+
     pub fn initialize_segments(&mut self, segments: &mut MemorySegmentManager) {
         let info = &[
             MaybeRelocatable::from(segments.add()),
@@ -165,6 +224,53 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn segments_limit() {
+        let mut builtin = SegmentArenaBuiltinRunner::new(true);
+        let mut vm = vm!();
+        builtin.initialize_segments(&mut vm.segments);
+        // Each instance is (infos, n_segments, n_finalized)
+        for (instance, n_segments) in [1, 2, 3].into_iter().enumerate() {
+            let addr = (builtin.base + instance * ARENA_BUILTIN_SIZE as usize).unwrap();
+            vm.segments
+                .load_data(
+                    addr,
+                    &[
+                        MaybeRelocatable::from(Relocatable::from((0, 0))),
+                        MaybeRelocatable::from(n_segments),
+                        MaybeRelocatable::from(0),
+                    ],
+                )
+                .unwrap();
+        }
+        assert_eq!(builtin.allocated_segments(&vm.segments.memory), 3);
+
+        // No rule is added without a limit
+        builtin.add_validation_rule(&mut vm.segments.memory);
+        assert_eq!(vm.segments.memory.validate_existing_memory(), Ok(()));
+
+        builtin.set_max_segments(Some(3));
+        builtin.add_validation_rule(&mut vm.segments.memory);
+        assert_eq!(vm.segments.memory.validate_existing_memory(), Ok(()));
+
+        // The limit is enforced as soon as the arena grows past it
+        let addr = (builtin.base + 3 * ARENA_BUILTIN_SIZE as usize).unwrap();
+        assert_eq!(
+            vm.segments.load_data(
+                addr,
+                &[
+                    MaybeRelocatable::from(Relocatable::from((0, 0))),
+                    MaybeRelocatable::from(4),
+                ],
+            ),
+            Err(MemoryError::SegmentArenaLimitExceeded(Box::new((
+                Felt252::from(4),
+                3
+            ))))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn final_stack_valid() {
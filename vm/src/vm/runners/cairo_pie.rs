@@ -225,6 +225,17 @@ impl CairoPieMetadata {
 }
 
 impl CairoPie {
+    /// Returns the extra segments carried by the Cairo PIE, that is, the segments which are not
+    /// the program, execution, return or builtin segments.
+    pub fn extra_segments(&self) -> &[SegmentInfo] {
+        &self.metadata.extra_segments
+    }
+
+    /// Returns the builtins used by the Cairo PIE's program, in the program's order.
+    pub fn builtins(&self) -> &[BuiltinName] {
+        &self.metadata.program.builtins
+    }
+
     /// Check that self is a valid Cairo PIE
     pub fn run_validity_checks(&self) -> Result<(), CairoPieValidationError> {
         self.metadata.run_validity_checks()?;
@@ -784,6 +795,34 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn serialize_additional_data_is_deterministic() {
+        let entries = [
+            BuiltinName::output,
+            BuiltinName::pedersen,
+            BuiltinName::range_check,
+            BuiltinName::ecdsa,
+            BuiltinName::bitwise,
+            BuiltinName::ec_op,
+            BuiltinName::keccak,
+            BuiltinName::poseidon,
+        ];
+        let additional_data = |names: &mut dyn Iterator<Item = &BuiltinName>| {
+            CairoPieAdditionalData(
+                names
+                    .map(|name| (*name, BuiltinAdditionalData::None))
+                    .collect(),
+            )
+        };
+        let forward = serde_json::to_string(&additional_data(&mut entries.iter())).unwrap();
+        let backward = serde_json::to_string(&additional_data(&mut entries.iter().rev())).unwrap();
+        assert_eq!(forward, backward);
+        assert_eq!(
+            forward,
+            r#"{"output_builtin":null,"pedersen_builtin":null,"range_check_builtin":null,"ecdsa_builtin":null,"bitwise_builtin":null,"ec_op_builtin":null,"keccak_builtin":null,"poseidon_builtin":null}"#
+        );
+    }
+
     #[test]
     fn serialize_cairo_pie_memory() {
         let addrs = [
@@ -8,16 +8,21 @@ use crate::{
         ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign},
         prelude::*,
     },
-    types::{builtin_name::BuiltinName, layout::CairoLayoutParams, layout_name::LayoutName},
+    types::{
+        builtin_name::{BuiltinName, BUILTIN_CANONICAL_ORDER},
+        layout::CairoLayoutParams,
+        layout_name::LayoutName,
+    },
     vm::{
         runners::builtin_runner::SegmentArenaBuiltinRunner,
-        trace::trace_entry::{relocate_trace_register, RelocatedTraceEntry},
+        trace::trace_entry::{relocate_trace, RelocatedTraceEntry},
     },
     Felt252,
 };
 
 use crate::{
     hint_processor::hint_processor_definition::{HintProcessor, HintReference},
+    serde::deserialize_program::get_entrypoint_names,
     types::{
         errors::{math_errors::MathError, program_errors::ProgramError},
         exec_scope::ExecutionScopes,
@@ -57,6 +62,8 @@ use super::{
     cairo_pie::{self, CairoPie, CairoPieMetadata, CairoPieVersion},
 };
 use crate::types::instance_definitions::mod_instance_def::ModInstanceDef;
+#[cfg(feature = "extensive_hints")]
+use crate::types::program::HintRange;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CairoArg {
@@ -107,6 +114,8 @@ pub trait ResourceTracker {
     fn run_resources(&self) -> &RunResources {
         &RunResources { n_steps: None }
     }
+    /// Replaces the available resources, used by `cairo_run_pie` when `override_pie_n_steps` is set
+    fn set_run_resources(&mut self, _run_resources: RunResources) {}
 }
 
 impl RunResources {
@@ -115,6 +124,12 @@ impl RunResources {
             n_steps: Some(n_steps),
         }
     }
+
+    /// Returns the resources needed to re-run a Cairo PIE with `cairo_run_pie`,
+    /// i.e. as many steps as the PIE's execution took.
+    pub fn for_pie(pie: &CairoPie) -> Self {
+        Self::new(pie.execution_resources.n_steps)
+    }
 }
 
 impl ResourceTracker for RunResources {
@@ -138,6 +153,10 @@ impl ResourceTracker for RunResources {
     fn run_resources(&self) -> &RunResources {
         self
     }
+
+    fn set_run_resources(&mut self, run_resources: RunResources) {
+        *self = run_resources;
+    }
 }
 
 pub struct CairoRunner {
@@ -153,11 +172,41 @@ pub struct CairoRunner {
     initial_pc: Option<Relocatable>,
     run_ended: bool,
     segments_finalized: bool,
+    paused_hint_pc: Option<Relocatable>,
     execution_public_memory: Option<Vec<usize>>,
     runner_mode: RunnerMode,
     pub relocated_memory: Vec<Option<Felt252>>,
     pub exec_scopes: ExecutionScopes,
     pub relocated_trace: Option<Vec<RelocatedTraceEntry>>,
+    hints: Option<RunHints>,
+    segment_arena_max_segments: Option<usize>,
+}
+
+/// Compiled hints of a run, along with the hints added dynamically during the run.
+/// They are only kept across calls while the run is paused on a hint, or if they were given to
+/// [`CairoRunner::new_from_prepared`].
+struct RunHints {
+    data: Vec<Box<dyn Any>>,
+    #[cfg(feature = "extensive_hints")]
+    ranges: HashMap<Relocatable, HintRange>,
+    prepared: bool,
+}
+
+impl RunHints {
+    fn new(program: &Program, data: Vec<Box<dyn Any>>) -> Self {
+        #[cfg(not(feature = "extensive_hints"))]
+        let _ = program;
+        RunHints {
+            data,
+            #[cfg(feature = "extensive_hints")]
+            ranges: program
+                .shared_program_data
+                .hints_collection
+                .hints_ranges
+                .clone(),
+            prepared: false,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -167,6 +216,26 @@ pub enum RunnerMode {
     ProofModeCairo1,
 }
 
+/// State returned by [`CairoRunner::run_until_next_hint`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunState {
+    /// Execution stopped right before running the hints at the given pc
+    PausedAtHint(Relocatable),
+    /// Execution reached the program's final pc
+    Finished,
+}
+
+/// Outcome of [`CairoRunner::run_for_steps`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// All the steps were executed and the last one reached the program's final pc
+    Completed,
+    /// All the steps were executed without reaching the program's final pc
+    ReachedLimit,
+    /// The program's final pc was reached with the given number of steps left to execute
+    ProgramEnded(usize),
+}
+
 impl CairoRunner {
     /// The `dynamic_layout_params` argument should only be used with dynamic layout.
     /// It is ignored otherwise.
@@ -195,7 +264,21 @@ impl CairoRunner {
                 CairoLayout::dynamic_instance(params)
             }
         };
-        Ok(CairoRunner {
+        Ok(Self::with_layout(
+            program,
+            cairo_layout,
+            mode,
+            trace_enabled,
+        ))
+    }
+
+    fn with_layout(
+        program: &Program,
+        cairo_layout: CairoLayout,
+        mode: RunnerMode,
+        trace_enabled: bool,
+    ) -> CairoRunner {
+        CairoRunner {
             program: program.clone(),
             vm: VirtualMachine::new(trace_enabled),
             layout: cairo_layout,
@@ -208,6 +291,7 @@ impl CairoRunner {
             initial_pc: None,
             run_ended: false,
             segments_finalized: false,
+            paused_hint_pc: None,
             runner_mode: mode.clone(),
             relocated_memory: Vec::new(),
             exec_scopes: ExecutionScopes::new(),
@@ -217,7 +301,9 @@ impl CairoRunner {
                 None
             },
             relocated_trace: None,
-        })
+            hints: None,
+            segment_arena_max_segments: None,
+        }
     }
 
     pub fn new(
@@ -246,6 +332,74 @@ impl CairoRunner {
         }
     }
 
+    /// Creates a runner that reuses hints compiled beforehand, as returned by
+    /// [`CairoRunner::compile_hints`], instead of compiling them again at the start of each run.
+    /// `compiled_hints` maps the index of each hint of the program, in pc order, to its compiled data.
+    /// The compiled hints can be recovered with [`CairoRunner::take_compiled_hints`] to prepare
+    /// the next run of the same program.
+    pub fn new_from_prepared(
+        program: &Program,
+        mut compiled_hints: HashMap<usize, Box<dyn Any>>,
+        layout: LayoutName,
+        dynamic_layout_params: Option<CairoLayoutParams>,
+        proof_mode: bool,
+        trace_enabled: bool,
+    ) -> Result<CairoRunner, RunnerError> {
+        let n_hints = program
+            .shared_program_data
+            .hints_collection
+            .iter_hints()
+            .count();
+        let hint_data = (0..n_hints)
+            .map(|index| {
+                compiled_hints
+                    .remove(&index)
+                    .ok_or(RunnerError::MissingCompiledHint(index))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut runner = Self::new(
+            program,
+            layout,
+            dynamic_layout_params,
+            proof_mode,
+            trace_enabled,
+        )?;
+        runner.hints = Some(RunHints {
+            prepared: true,
+            ..RunHints::new(program, hint_data)
+        });
+        Ok(runner)
+    }
+
+    /// Compiles the hints of a program with the given hint processor, mapping the index of each
+    /// hint, in pc order, to its compiled data. See [`CairoRunner::new_from_prepared`].
+    pub fn compile_hints(
+        program: &Program,
+        hint_processor: &dyn HintProcessor,
+    ) -> Result<HashMap<usize, Box<dyn Any>>, VirtualMachineError> {
+        let references = &program.shared_program_data.reference_manager;
+        Self::compile_program_hints(program, references, hint_processor)
+            .map(|hint_data| hint_data.into_iter().enumerate().collect())
+    }
+
+    /// Returns the compiled hints the runner was created with by [`CairoRunner::new_from_prepared`],
+    /// so that they can be reused by another runner. Runs started afterwards compile the hints again.
+    pub fn take_compiled_hints(&mut self) -> HashMap<usize, Box<dyn Any>> {
+        let n_hints = self
+            .program
+            .shared_program_data
+            .hints_collection
+            .iter_hints()
+            .count();
+        self.hints
+            .take()
+            .map(|hints| hints.data.into_iter().take(n_hints).enumerate().collect())
+            .unwrap_or_default()
+    }
+
+    /// Initializes the builtins, segments, entrypoint and vm.
+    /// Fails with `RunnerError::BuiltinNotInLayout` if any program builtin is missing from the
+    /// layout, unless `allow_missing_builtins` is set.
     pub fn initialize(&mut self, allow_missing_builtins: bool) -> Result<Relocatable, RunnerError> {
         self.initialize_builtins(allow_missing_builtins)?;
         self.initialize_segments(None);
@@ -264,20 +418,9 @@ impl CairoRunner {
     /// When not running in proof_mode, only program builtins will be created and included
     /// Unless `allow_missing_builtins` is set to true, an error will be returned if a builtin is included in the program but not on the layout
     pub fn initialize_builtins(&mut self, allow_missing_builtins: bool) -> Result<(), RunnerError> {
-        let builtin_ordered_list = vec![
-            BuiltinName::output,
-            BuiltinName::pedersen,
-            BuiltinName::range_check,
-            BuiltinName::ecdsa,
-            BuiltinName::bitwise,
-            BuiltinName::ec_op,
-            BuiltinName::keccak,
-            BuiltinName::poseidon,
-            BuiltinName::range_check96,
-            BuiltinName::add_mod,
-            BuiltinName::mul_mod,
-        ];
-        if !is_subsequence(&self.program.builtins, &builtin_ordered_list) {
+        // segment_arena is not supported by any layout
+        let builtin_ordered_list = &BUILTIN_CANONICAL_ORDER[..BUILTIN_CANONICAL_ORDER.len() - 1];
+        if !is_subsequence(&self.program.builtins, builtin_ordered_list) {
             return Err(RunnerError::DisorderedBuiltins);
         };
         let mut program_builtins: HashSet<&BuiltinName> = self.program.builtins.iter().collect();
@@ -334,9 +477,11 @@ impl CairoRunner {
         if let Some(instance_def) = self.layout.builtins.ec_op.as_ref() {
             let included = program_builtins.remove(&BuiltinName::ec_op);
             if included || self.is_proof_mode() {
-                self.vm
-                    .builtin_runners
-                    .push(EcOpBuiltinRunner::new(instance_def.ratio, included).into());
+                self.vm.builtin_runners.push(
+                    EcOpBuiltinRunner::new(instance_def.ratio, included)
+                        .with_scalar_height(instance_def.scalar_height)
+                        .into(),
+                );
             }
         }
 
@@ -387,8 +532,13 @@ impl CairoRunner {
             }
         }
         if !program_builtins.is_empty() && !allow_missing_builtins {
-            return Err(RunnerError::NoBuiltinForInstance(Box::new((
-                program_builtins.iter().map(|n| **n).collect(),
+            return Err(RunnerError::BuiltinNotInLayout(Box::new((
+                self.program
+                    .builtins
+                    .iter()
+                    .filter(|builtin| program_builtins.contains(builtin))
+                    .copied()
+                    .collect(),
                 self.layout.name,
             ))));
         }
@@ -450,6 +600,7 @@ impl CairoRunner {
         for builtin_name in &self.program.builtins {
             initialize_builtin(*builtin_name, &mut self.vm);
         }
+        self.set_segment_arena_max_segments(self.segment_arena_max_segments);
         Ok(())
     }
 
@@ -621,22 +772,73 @@ impl CairoRunner {
             .map_err(RunnerError::MemoryValidationError)
     }
 
+    /// Writes `data` into memory starting at `base`, returning the first address after it.
+    /// Meant to preload memory after `initialize` and before running the program. The values
+    /// are checked against the validation rules of the segment they are written to.
+    pub fn load_data(
+        &mut self,
+        base: Relocatable,
+        data: &[MaybeRelocatable],
+    ) -> Result<Relocatable, MemoryError> {
+        self.vm.load_data(base, data)
+    }
+
+    /// Maps an address of the relocated memory back to the `(segment_index, offset)` it had
+    /// during the run, using the relocation table computed by [`CairoRunner::relocate`].
+    /// Returns None if the runner hasn't been relocated or the address belongs to no segment.
+    pub fn unrelocate_address(&self, flat: usize) -> Option<Relocatable> {
+        let relocation_table = self.vm.relocation_table.as_ref()?;
+        // Empty segments share their start with the next one, pick the last segment starting
+        // at or before the address
+        let segment_index = relocation_table
+            .partition_point(|start| *start <= flat)
+            .checked_sub(1)?;
+        let offset = flat - relocation_table[segment_index];
+        (offset < self.vm.segments.get_segment_size(segment_index)?)
+            .then(|| Relocatable::from((segment_index as isize, offset)))
+    }
+
     pub fn get_initial_fp(&self) -> Option<Relocatable> {
         self.initial_fp
     }
 
+    /// Returns the `(pc, ap, fp)` registers at the current point of the run.
+    /// After `run_until_pc` (and the extra step taken in proof mode) these are the final registers
+    pub fn final_state(&self) -> (Relocatable, Relocatable, Relocatable) {
+        (self.vm.get_pc(), self.vm.get_ap(), self.vm.get_fp())
+    }
+
     /// Gets the data used by the HintProcessor to execute each hint
     pub fn get_hint_data(
         &self,
         references: &[HintReference],
         hint_executor: &mut dyn HintProcessor,
     ) -> Result<Vec<Box<dyn Any>>, VirtualMachineError> {
-        self.program
+        Self::compile_program_hints(&self.program, references, hint_executor)
+    }
+
+    /// Compiles every hint in the program upfront, so that hints that fail to compile are
+    /// detected before execution starts instead of when their pc is reached
+    pub fn precompile_all_hints(
+        &self,
+        hint_processor: &dyn HintProcessor,
+    ) -> Result<(), VirtualMachineError> {
+        let references = &self.program.shared_program_data.reference_manager;
+        Self::compile_program_hints(&self.program, references, hint_processor).map(|_| ())
+    }
+
+    /// Compiles the hints of `program`, in pc order
+    fn compile_program_hints(
+        program: &Program,
+        references: &[HintReference],
+        hint_processor: &dyn HintProcessor,
+    ) -> Result<Vec<Box<dyn Any>>, VirtualMachineError> {
+        program
             .shared_program_data
             .hints_collection
             .iter_hints()
             .map(|hint| {
-                hint_executor
+                hint_processor
                     .compile_hint(
                         &hint.code,
                         &hint.flow_tracking_data.ap_tracking,
@@ -661,37 +863,101 @@ impl CairoRunner {
         address: Relocatable,
         hint_processor: &mut dyn HintProcessor,
     ) -> Result<(), VirtualMachineError> {
-        let references = &self.program.shared_program_data.reference_manager;
-        #[cfg(not(feature = "extensive_hints"))]
-        let hint_data = self.get_hint_data(references, hint_processor)?;
-        #[cfg(feature = "extensive_hints")]
-        let mut hint_data = self.get_hint_data(references, hint_processor)?;
-        #[cfg(feature = "extensive_hints")]
-        let mut hint_ranges = self
-            .program
-            .shared_program_data
-            .hints_collection
-            .hints_ranges
-            .clone();
+        self.run_until(address, false, hint_processor).map(|_| ())
+    }
+
+    /// Runs the program until the next pc containing hints is reached, without executing them.
+    /// Calling it again executes the pending hints and continues until the next hint or the end
+    /// of the program, so that running it until [`RunState::Finished`] is equivalent to calling
+    /// `run_until_pc` with the program's final pc.
+    pub fn run_until_next_hint(
+        &mut self,
+        hint_processor: &mut dyn HintProcessor,
+    ) -> Result<RunState, VirtualMachineError> {
+        let final_pc = self.final_pc.ok_or(RunnerError::NoFinalPC)?;
+        self.run_until(final_pc, true, hint_processor)
+    }
+
+    /// Returns the hints of the run, compiling them with `hint_processor` unless they were kept by
+    /// the previous call. They must be handed back with `keep_hints` once the call is over.
+    fn take_hints(
+        &mut self,
+        hint_processor: &mut dyn HintProcessor,
+    ) -> Result<RunHints, VirtualMachineError> {
+        match self.hints.take() {
+            Some(hints) => Ok(hints),
+            None => {
+                let references = &self.program.shared_program_data.reference_manager;
+                let data = self.get_hint_data(references, hint_processor)?;
+                Ok(RunHints::new(&self.program, data))
+            }
+        }
+    }
+
+    /// Keeps the hints for the next call if they were prepared or the run is paused on a hint,
+    /// so that other calls compile them with the hint processor they are given.
+    fn keep_hints(&mut self, hints: RunHints) {
+        if hints.prepared || self.paused_hint_pc.is_some() {
+            self.hints = Some(hints);
+        }
+    }
+
+    fn run_until(
+        &mut self,
+        address: Relocatable,
+        pause_on_hints: bool,
+        hint_processor: &mut dyn HintProcessor,
+    ) -> Result<RunState, VirtualMachineError> {
+        let mut hints = self.take_hints(hint_processor)?;
+        let result = self.run_hints_until(address, pause_on_hints, &mut hints, hint_processor);
+        self.keep_hints(hints);
+        result
+    }
+
+    fn run_hints_until(
+        &mut self,
+        address: Relocatable,
+        pause_on_hints: bool,
+        hints: &mut RunHints,
+        hint_processor: &mut dyn HintProcessor,
+    ) -> Result<RunState, VirtualMachineError> {
+        // Hints at the pc we paused on during the previous call are executed right away
+        let resume_pc = self.paused_hint_pc.take();
         #[cfg(feature = "test_utils")]
-        self.vm.execute_before_first_step(&hint_data)?;
+        if resume_pc.is_none() {
+            self.vm.execute_before_first_step(&hints.data)?;
+        }
+        let mut resume_pc = resume_pc;
         while self.vm.get_pc() != address && !hint_processor.consumed() {
+            let pc = self.vm.get_pc();
+            #[cfg(not(feature = "extensive_hints"))]
+            let hint_range = self
+                .program
+                .shared_program_data
+                .hints_collection
+                .get_hint_range_for_pc(pc.offset)
+                .flatten();
+            #[cfg(not(feature = "extensive_hints"))]
+            let has_hints = hint_range.is_some();
+            #[cfg(feature = "extensive_hints")]
+            let has_hints = pause_on_hints && hints.ranges.contains_key(&pc);
+            if pause_on_hints && has_hints && resume_pc != Some(pc) {
+                self.paused_hint_pc = Some(pc);
+                return Ok(RunState::PausedAtHint(pc));
+            }
+            resume_pc = None;
+
             self.vm.step(
                 hint_processor,
                 &mut self.exec_scopes,
                 #[cfg(feature = "extensive_hints")]
-                &mut hint_data,
+                &mut hints.data,
                 #[cfg(not(feature = "extensive_hints"))]
-                self.program
-                    .shared_program_data
-                    .hints_collection
-                    .get_hint_range_for_pc(self.vm.get_pc().offset)
-                    .and_then(|range| {
-                        range.and_then(|(start, length)| hint_data.get(start..start + length.get()))
-                    })
+                hint_range
+                    .and_then(|(start, length)| hints.data.get(start..start + length.get()))
                     .unwrap_or(&[]),
                 #[cfg(feature = "extensive_hints")]
-                &mut hint_ranges,
+                &mut hints.ranges,
                 &self.program.constants,
             )?;
 
@@ -702,66 +968,80 @@ impl CairoRunner {
             return Err(VirtualMachineError::UnfinishedExecution);
         }
 
-        Ok(())
+        Ok(RunState::Finished)
     }
 
     /// Execute an exact number of steps on the program from the actual position.
+    /// Stops early if the program's final pc is reached, reporting the steps left in
+    /// [`StepOutcome::ProgramEnded`].
     pub fn run_for_steps(
         &mut self,
         steps: usize,
         hint_processor: &mut dyn HintProcessor,
-    ) -> Result<(), VirtualMachineError> {
-        let references = &self.program.shared_program_data.reference_manager;
-        #[cfg(not(feature = "extensive_hints"))]
-        let hint_data = self.get_hint_data(references, hint_processor)?;
-        #[cfg(feature = "extensive_hints")]
-        let mut hint_data = self.get_hint_data(references, hint_processor)?;
-        #[cfg(feature = "extensive_hints")]
-        let mut hint_ranges = self
-            .program
-            .shared_program_data
-            .hints_collection
-            .hints_ranges
-            .clone();
+    ) -> Result<StepOutcome, VirtualMachineError> {
+        let mut hints = self.take_hints(hint_processor)?;
+        let result = self.run_hints_for_steps(steps, &mut hints, hint_processor);
+        self.keep_hints(hints);
+        result
+    }
+
+    fn run_hints_for_steps(
+        &mut self,
+        steps: usize,
+        hints: &mut RunHints,
+        hint_processor: &mut dyn HintProcessor,
+    ) -> Result<StepOutcome, VirtualMachineError> {
         #[cfg(not(feature = "extensive_hints"))]
-        let hint_data = &self
+        let hint_data = self
             .program
             .shared_program_data
             .hints_collection
             .get_hint_range_for_pc(self.vm.get_pc().offset)
             .and_then(|range| {
-                range.and_then(|(start, length)| hint_data.get(start..start + length.get()))
+                range.and_then(|(start, length)| hints.data.get(start..start + length.get()))
             })
             .unwrap_or(&[]);
 
         for remaining_steps in (1..=steps).rev() {
             if self.final_pc.as_ref() == Some(&self.vm.get_pc()) {
-                return Err(VirtualMachineError::EndOfProgram(remaining_steps));
+                return Ok(StepOutcome::ProgramEnded(remaining_steps));
             }
+            // Stepping runs the hints we paused on, if any, so the run is no longer paused
+            self.paused_hint_pc = None;
 
             self.vm.step(
                 hint_processor,
                 &mut self.exec_scopes,
                 #[cfg(feature = "extensive_hints")]
-                &mut hint_data,
+                &mut hints.data,
                 #[cfg(not(feature = "extensive_hints"))]
                 hint_data,
                 #[cfg(feature = "extensive_hints")]
-                &mut hint_ranges,
+                &mut hints.ranges,
                 &self.program.constants,
             )?;
         }
 
-        Ok(())
+        if self.final_pc.as_ref() == Some(&self.vm.get_pc()) {
+            Ok(StepOutcome::Completed)
+        } else {
+            Ok(StepOutcome::ReachedLimit)
+        }
     }
 
     /// Execute steps until a number of steps since the start of the program is reached.
+    /// Fails with [`VirtualMachineError::EndOfProgram`] if the program ends before that.
     pub fn run_until_steps(
         &mut self,
         steps: usize,
         hint_processor: &mut dyn HintProcessor,
     ) -> Result<(), VirtualMachineError> {
-        self.run_for_steps(steps.saturating_sub(self.vm.current_step), hint_processor)
+        match self.run_for_steps(steps.saturating_sub(self.vm.current_step), hint_processor)? {
+            StepOutcome::ProgramEnded(remaining_steps) => {
+                Err(VirtualMachineError::EndOfProgram(remaining_steps))
+            }
+            StepOutcome::Completed | StepOutcome::ReachedLimit => Ok(()),
+        }
     }
 
     /// Execute steps until the step counter reaches a power of two.
@@ -813,6 +1093,15 @@ impl CairoRunner {
         Ok(())
     }
 
+    /// Computes and caches the used size of each memory segment, so that queries depending on
+    /// them (such as [BuiltinRunner::get_used_cells]) can be made before the run has ended.
+    /// Any previously cached sizes are discarded, as the run may have advanced since they were
+    /// computed. `end_run` recomputes them too, so they reflect the whole run once it has ended.
+    pub fn compute_segment_sizes(&mut self) {
+        self.vm.segments.segment_used_sizes = None;
+        self.vm.segments.compute_effective_sizes();
+    }
+
     /// Count the number of holes present in the segments.
     pub fn get_memory_holes(&self) -> Result<usize, MemoryError> {
         // Grab builtin segment indexes, except for the output builtin
@@ -888,6 +1177,8 @@ impl CairoRunner {
             return Ok(());
         }
 
+        // Discard any sizes cached by compute_segment_sizes before the run ended
+        self.vm.segments.segment_used_sizes = None;
         self.vm.segments.compute_effective_sizes();
         if self.is_proof_mode() && !disable_trace_padding {
             self.run_until_next_power_of_2(hint_processor)?;
@@ -916,24 +1207,8 @@ impl CairoRunner {
             return Err(TraceError::AlreadyRelocated);
         }
 
-        let trace = self
-            .vm
-            .trace
-            .as_ref()
-            .ok_or(TraceError::TraceNotEnabled)?
-            .iter();
-        let mut relocated_trace = Vec::<RelocatedTraceEntry>::with_capacity(trace.len());
-        let segment_1_base = relocation_table
-            .get(1)
-            .ok_or(TraceError::NoRelocationFound)?;
-
-        for entry in trace {
-            relocated_trace.push(RelocatedTraceEntry {
-                pc: relocate_trace_register(entry.pc, relocation_table)?,
-                ap: entry.ap + segment_1_base,
-                fp: entry.fp + segment_1_base,
-            })
-        }
+        let trace = self.vm.trace.as_ref().ok_or(TraceError::TraceNotEnabled)?;
+        let relocated_trace = relocate_trace(trace, relocation_table)?;
         self.relocated_trace = Some(relocated_trace);
         Ok(())
     }
@@ -1033,6 +1308,24 @@ impl CairoRunner {
         Ok(builtin_segment_info)
     }
 
+    /// Sets the maximum number of segments the segment arena builtin can allocate, enforced while
+    /// running the program. Must be set before the vm is initialized. There is no limit by default.
+    pub fn set_segment_arena_max_segments(&mut self, max_segments: Option<usize>) {
+        self.segment_arena_max_segments = max_segments;
+        for builtin in self.vm.builtin_runners.iter_mut() {
+            if let BuiltinRunner::SegmentArena(segment_arena) = builtin {
+                segment_arena.set_max_segments(max_segments);
+            }
+        }
+    }
+
+    /// Returns the time spent by each builtin deducing memory cells during the run.
+    /// Profiling must be enabled beforehand, see `VirtualMachine::enable_builtin_profiling`.
+    #[cfg(feature = "profile_builtins")]
+    pub fn builtin_timings(&self) -> HashMap<BuiltinName, std::time::Duration> {
+        self.vm.builtin_timings()
+    }
+
     pub fn get_execution_resources(&self) -> Result<ExecutionResources, RunnerError> {
         let n_steps = self
             .vm
@@ -1118,6 +1411,40 @@ impl CairoRunner {
         Ok(())
     }
 
+    /// Returns the relocated (address, value) pairs of the public memory, made up of the program
+    /// segment, the execution stack prefix (including the builtin pointers) and the output pages.
+    /// Must be called after `finalize_segments` and `relocate`.
+    pub fn get_public_memory(&self) -> Result<Vec<(usize, Felt252)>, RunnerError> {
+        if !self.segments_finalized {
+            return Err(RunnerError::PublicMemoryNoFinalizeSegments);
+        }
+        let relocation_table = self
+            .vm
+            .relocation_table
+            .as_ref()
+            .ok_or(MemoryError::UnrelocatedMemory)?;
+        let mut public_memory = Vec::new();
+        for segment_index in 0..self.vm.segments.num_segments() {
+            let Some(offsets) = self.vm.segments.public_memory_offsets.get(&segment_index) else {
+                continue;
+            };
+            for (offset, _page_id) in offsets {
+                let addr = Relocatable::from((segment_index as isize, *offset));
+                let value = self
+                    .vm
+                    .segments
+                    .memory
+                    .get(&addr)
+                    .ok_or_else(|| MemoryError::UnknownMemoryCell(Box::new(addr)))?;
+                public_memory.push((
+                    relocate_address(addr, relocation_table)?,
+                    relocate_value(value.into_owned(), relocation_table)?,
+                ));
+            }
+        }
+        Ok(public_memory)
+    }
+
     /// Runs a cairo program from a give entrypoint, indicated by its pc offset, with the given arguments.
     /// If `verify_secure` is set to true, [verify_secure_runner] will be called to run extra verifications.
     /// `program_segment_size` is only used by the [verify_secure_runner] function and will be ignored if `verify_secure` is set to false.
@@ -1149,6 +1476,72 @@ impl CairoRunner {
         Ok(())
     }
 
+    /// Runs the security checks of [verify_secure_runner] on this runner, including the builtin
+    /// segment checks, for runners built through the lower-level APIs instead of `cairo_run`.
+    pub fn verify_secure(&self) -> Result<(), VirtualMachineError> {
+        verify_secure_runner(self, true, None)
+    }
+
+    /// Checks that the program can be run on this runner's layout without executing it, so that
+    /// programs can be validated even when their hints are not available.
+    /// Initializes a copy of the runner, which catches a missing main and builtins that are
+    /// disordered or unsupported by the layout, and checks that the entrypoint is within the
+    /// program data and that the references used by the hints exist.
+    pub fn validate_program_only(&self) -> Result<(), RunnerError> {
+        let mut runner = Self::with_layout(
+            &self.program,
+            self.layout.clone(),
+            self.runner_mode.clone(),
+            false,
+        );
+        runner.entrypoint = self.entrypoint;
+        runner.initialize(false)?;
+
+        let data_len = self.program.data_len();
+        if let Some(entrypoint) = self.entrypoint.filter(|entrypoint| *entrypoint >= data_len) {
+            return Err(RunnerError::EntrypointOutOfBounds(Box::new((
+                entrypoint, data_len,
+            ))));
+        }
+
+        let references_len = self.program.shared_program_data.reference_manager.len();
+        for hint in self
+            .program
+            .shared_program_data
+            .hints_collection
+            .iter_hints()
+        {
+            for (name, id) in hint.flow_tracking_data.reference_ids.iter() {
+                if *id >= references_len {
+                    return Err(RunnerError::MissingHintReference(Box::new((
+                        name.clone(),
+                        *id,
+                    ))));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that ap never decreases along the relocated trace, which would indicate a bug.
+    /// Returns the first step at which ap decreased, along with its previous and current values.
+    pub fn check_ap_monotonic(&self) -> Result<(), VirtualMachineError> {
+        let trace = self
+            .relocated_trace
+            .as_ref()
+            .ok_or(TraceError::TraceNotRelocated)?;
+        for (step, entries) in trace.windows(2).enumerate() {
+            if entries[1].ap < entries[0].ap {
+                return Err(VirtualMachineError::ApDecreased {
+                    step: step + 1,
+                    prev: entries[0].ap,
+                    curr: entries[1].ap,
+                });
+            }
+        }
+        Ok(())
+    }
+
     // Returns Ok(()) if there are enough allocated cells for the builtins.
     // If not, the number of steps should be increased or a different layout should be used.
     pub fn check_used_cells(&self) -> Result<(), VirtualMachineError> {
@@ -1240,7 +1633,12 @@ impl CairoRunner {
                 .identifiers
                 .get(&format!("__main__.{new_entrypoint}"))
                 .and_then(|x| x.pc)
-                .ok_or_else(|| ProgramError::EntrypointNotFound(new_entrypoint.to_string()))?,
+                .ok_or_else(|| {
+                    ProgramError::EntrypointNotFound(
+                        new_entrypoint.to_string(),
+                        get_entrypoint_names(&self.program.shared_program_data.identifiers),
+                    )
+                })?,
         );
 
         Ok(())
@@ -1578,14 +1976,16 @@ mod tests {
     use super::*;
     use crate::air_private_input::{PrivateInput, PrivateInputSignature, SignatureInput};
     use crate::cairo_run::{cairo_run, CairoRunConfig};
-    use crate::stdlib::collections::{HashMap, HashSet};
+    use crate::stdlib::collections::HashMap;
     use crate::vm::vm_memory::memory::MemoryCell;
 
     use crate::felt_hex;
     use crate::{
         hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
         relocatable,
-        serde::deserialize_program::{Identifier, ReferenceManager},
+        serde::deserialize_program::{
+            ApTracking, FlowTrackingData, HintParams, Identifier, ReferenceManager,
+        },
         utils::test_utils::*,
         vm::trace::trace_entry::TraceEntry,
     };
@@ -1594,6 +1994,63 @@ mod tests {
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn verify_secure_builtin_access_out_of_bounds() {
+        let program = program!(main = Some(0), builtins = vec![BuiltinName::range_check],);
+        let mut runner = cairo_runner!(program);
+
+        runner.initialize(false).unwrap();
+        runner.vm.segments.segment_used_sizes = Some(vec![0, 0, 0, 0]);
+        runner.vm.builtin_runners[0].set_stop_ptr(1);
+        runner.vm.segments.memory = memory![((2, 0), 1)];
+        assert_matches!(runner.verify_secure(), Ok(()));
+
+        // Write past the range_check segment's stop pointer
+        runner.vm.builtin_runners[0].set_stop_ptr(0);
+        assert_matches!(
+            runner.verify_secure(),
+            Err(VirtualMachineError::OutOfBoundsBuiltinSegmentAccess)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn check_ap_monotonic() {
+        let program = program!();
+        let mut cairo_runner = cairo_runner!(program);
+        assert_matches!(
+            cairo_runner.check_ap_monotonic(),
+            Err(VirtualMachineError::TracerError(
+                TraceError::TraceNotRelocated
+            ))
+        );
+
+        let entry = |pc, ap, fp| RelocatedTraceEntry { pc, ap, fp };
+        cairo_runner.relocated_trace = Some(vec![
+            entry(1, 10, 10),
+            entry(3, 11, 10),
+            entry(5, 11, 10),
+            entry(7, 14, 12),
+        ]);
+        assert_matches!(cairo_runner.check_ap_monotonic(), Ok(()));
+
+        cairo_runner.relocated_trace = Some(vec![
+            entry(1, 10, 10),
+            entry(3, 11, 10),
+            entry(5, 9, 10),
+            entry(7, 8, 10),
+        ]);
+        assert_matches!(
+            cairo_runner.check_ap_monotonic(),
+            Err(VirtualMachineError::ApDecreased {
+                step: 2,
+                prev: 11,
+                curr: 9
+            })
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn check_memory_usage_ok_case() {
@@ -1646,7 +2103,7 @@ mod tests {
         let mut cairo_runner = cairo_runner!(program, LayoutName::plain);
         assert_matches!(
             cairo_runner.initialize_builtins(false),
-            Err(RunnerError::NoBuiltinForInstance(_))
+            Err(RunnerError::BuiltinNotInLayout(_))
         )
     }
 
@@ -3330,6 +3787,69 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn initialize_builtins_order_is_deterministic() {
+        let program = program![
+            BuiltinName::output,
+            BuiltinName::pedersen,
+            BuiltinName::range_check,
+            BuiltinName::bitwise,
+            BuiltinName::ec_op,
+            BuiltinName::poseidon
+        ];
+        let builtin_names = |proof_mode: bool| -> Vec<BuiltinName> {
+            let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, proof_mode);
+            cairo_runner.initialize_builtins(false).unwrap();
+            let names: Vec<BuiltinName> = cairo_runner
+                .vm
+                .builtin_runners
+                .iter()
+                .map(|b| b.name())
+                .collect();
+            let ordered_names: Vec<BuiltinName> = cairo_runner
+                .vm
+                .builtin_runners_ordered()
+                .iter()
+                .map(|b| b.name())
+                .collect();
+            assert_eq!(names, ordered_names);
+            names
+        };
+
+        assert_eq!(builtin_names(false), builtin_names(false));
+        assert_eq!(builtin_names(true), builtin_names(true));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn builtin_segments_are_deterministic() {
+        let program = program![
+            BuiltinName::output,
+            BuiltinName::pedersen,
+            BuiltinName::range_check,
+            BuiltinName::bitwise,
+            BuiltinName::ec_op,
+            BuiltinName::poseidon
+        ];
+        let builtin_segments = || -> Vec<(BuiltinName, usize)> {
+            let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, true);
+            cairo_runner.initialize_builtins(false).unwrap();
+            cairo_runner.initialize_segments(None);
+            cairo_runner
+                .vm
+                .builtin_runners_ordered()
+                .iter()
+                .map(|b| (b.name(), b.base()))
+                .collect()
+        };
+
+        let segments = builtin_segments();
+        assert_eq!(segments, builtin_segments());
+        // Builtin segments are assigned following the canonical order
+        assert!(segments.windows(2).all(|pair| pair[0].1 < pair[1].1));
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     /*Program used:
@@ -3385,26 +3905,347 @@ mod tests {
         cairo_runner.initialize_vm().unwrap();
 
         // Full takes 10 steps.
-        assert_matches!(cairo_runner.run_for_steps(8, &mut hint_processor), Ok(()));
         assert_matches!(
             cairo_runner.run_for_steps(8, &mut hint_processor),
-            Err(VirtualMachineError::EndOfProgram(x)) if x == 8 - 2
+            Ok(StepOutcome::ReachedLimit)
         );
-    }
-
-    #[test]
-    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
-    fn run_empty() {
-        let program = program!();
-        let mut cairo_runner = cairo_runner!(&program, LayoutName::all_cairo, false, true);
         assert_matches!(
-            cairo_runner.initialize(false),
-            Err(RunnerError::MissingMain)
+            cairo_runner.run_for_steps(8, &mut hint_processor),
+            Ok(StepOutcome::ProgramEnded(x)) if x == 8 - 2
         );
-    }
 
-    #[test]
-    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        cairo_runner.initialize_builtins(false).unwrap();
+        cairo_runner.initialize_segments(None);
+        cairo_runner.initialize_main_entrypoint().unwrap();
+        cairo_runner.initialize_vm().unwrap();
+
+        assert_matches!(
+            cairo_runner.run_for_steps(9, &mut hint_processor),
+            Ok(StepOutcome::ReachedLimit)
+        );
+        assert_matches!(
+            cairo_runner.run_for_steps(1, &mut hint_processor),
+            Ok(StepOutcome::Completed)
+        );
+        assert_matches!(
+            cairo_runner.run_for_steps(3, &mut hint_processor),
+            Ok(StepOutcome::ProgramEnded(3))
+        );
+        assert_eq!(cairo_runner.vm.current_step, 10);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    /*Program used: same as run_for_steps
+     */
+    fn compute_segment_sizes_after_partial_run() {
+        let program = program!(
+            builtins = vec![BuiltinName::range_check],
+            data = vec_data!(
+                (4612671182993129469_i64),
+                (5189976364521848832_i64),
+                (18446744073709551615_i128),
+                (5199546496550207487_i64),
+                (4612389712311386111_i64),
+                (5198983563776393216_i64),
+                (2),
+                (2345108766317314046_i64),
+                (5191102247248822272_i64),
+                (5189976364521848832_i64),
+                (7),
+                (1226245742482522112_i64),
+                ((
+                    "3618502788666131213697322783095070105623107215331596699973092056135872020470",
+                    10
+                )),
+                (2345108766317314046_i64)
+            ),
+            main = Some(8),
+        );
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        cairo_runner.initialize_builtins(false).unwrap();
+        cairo_runner.initialize_segments(None);
+
+        cairo_runner.initialize_main_entrypoint().unwrap();
+        cairo_runner.initialize_vm().unwrap();
+
+        assert_matches!(
+            cairo_runner.run_for_steps(3, &mut hint_processor),
+            Ok(StepOutcome::ReachedLimit)
+        );
+        let range_check = &cairo_runner.vm.builtin_runners[0];
+        assert_matches!(
+            range_check.get_used_cells(&cairo_runner.vm.segments),
+            Err(MemoryError::MissingSegmentUsedSizes)
+        );
+
+        cairo_runner.compute_segment_sizes();
+        let range_check = &cairo_runner.vm.builtin_runners[0];
+        assert_eq!(range_check.get_used_cells(&cairo_runner.vm.segments), Ok(0));
+        assert_eq!(cairo_runner.vm.segments.get_segment_used_size(1), Some(7));
+
+        // Ending the run discards the sizes cached during the partial run
+        assert_matches!(
+            cairo_runner.run_for_steps(7, &mut hint_processor),
+            Ok(StepOutcome::Completed)
+        );
+        cairo_runner
+            .end_run(false, false, &mut hint_processor)
+            .unwrap();
+        let range_check = &cairo_runner.vm.builtin_runners[0];
+        assert_eq!(range_check.get_used_cells(&cairo_runner.vm.segments), Ok(2));
+        assert_eq!(cairo_runner.vm.segments.get_segment_used_size(1), Some(10));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    /*Program used: same as run_for_steps
+     */
+    fn run_with_dynamic_layout_params_built_in_code() {
+        let program = program!(
+            builtins = vec![BuiltinName::range_check],
+            data = vec_data!(
+                (4612671182993129469_i64),
+                (5189976364521848832_i64),
+                (18446744073709551615_i128),
+                (5199546496550207487_i64),
+                (4612389712311386111_i64),
+                (5198983563776393216_i64),
+                (2),
+                (2345108766317314046_i64),
+                (5191102247248822272_i64),
+                (5189976364521848832_i64),
+                (7),
+                (1226245742482522112_i64),
+                ((
+                    "3618502788666131213697322783095070105623107215331596699973092056135872020470",
+                    10
+                )),
+                (2345108766317314046_i64)
+            ),
+            main = Some(8),
+        );
+        let params = CairoLayoutParams {
+            rc_units: 4,
+            cpu_component_step: 1,
+            memory_units_per_step: 8,
+            log_diluted_units_per_step: 4,
+            pedersen_ratio: 0,
+            range_check_ratio: 8,
+            ecdsa_ratio: 0,
+            bitwise_ratio: 0,
+            ec_op_ratio: 0,
+            ec_op_scalar_height: None,
+            keccak_ratio: 0,
+            poseidon_ratio: 0,
+            range_check96_ratio: 0,
+            range_check96_ratio_den: 1,
+            add_mod_ratio: 0,
+            add_mod_ratio_den: 1,
+            mul_mod_ratio: 0,
+            mul_mod_ratio_den: 1,
+        };
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner =
+            CairoRunner::new(&program, LayoutName::dynamic, Some(params), false, false).unwrap();
+        let end = cairo_runner.initialize(false).unwrap();
+        assert_matches!(cairo_runner.run_until_pc(end, &mut hint_processor), Ok(()));
+
+        assert_eq!(cairo_runner.vm.builtin_runners.len(), 1);
+        assert_eq!(cairo_runner.vm.builtin_runners[0].ratio(), Some(8));
+        assert_matches!(
+            CairoRunner::new(&program, LayoutName::dynamic, None, false, false).err(),
+            Some(RunnerError::MissingDynamicLayoutParams)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    /*Program used: same as run_for_steps, with hints added at the start of main and check_range
+     */
+    fn run_until_next_hint_matches_run_until_pc() {
+        let program = program!(
+            builtins = vec![BuiltinName::range_check],
+            data = vec_data!(
+                (4612671182993129469_i64),
+                (5189976364521848832_i64),
+                (18446744073709551615_i128),
+                (5199546496550207487_i64),
+                (4612389712311386111_i64),
+                (5198983563776393216_i64),
+                (2),
+                (2345108766317314046_i64),
+                (5191102247248822272_i64),
+                (5189976364521848832_i64),
+                (7),
+                (1226245742482522112_i64),
+                ((
+                    "3618502788666131213697322783095070105623107215331596699973092056135872020470",
+                    10
+                )),
+                (2345108766317314046_i64)
+            ),
+            hints = BTreeMap::from([
+                (0, vec![hint_params("vm_exit_scope()", HashMap::new())]),
+                (8, vec![hint_params("vm_enter_scope()", HashMap::new())]),
+            ]),
+            main = Some(8),
+        );
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut expected_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let end = expected_runner.initialize(false).unwrap();
+        expected_runner
+            .run_until_pc(end, &mut hint_processor)
+            .unwrap();
+
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        cairo_runner.initialize(false).unwrap();
+        assert_matches!(
+            cairo_runner.run_until_next_hint(&mut hint_processor),
+            Ok(RunState::PausedAtHint(relocatable!(0, 8)))
+        );
+        // Hints have not been executed yet
+        assert_eq!(cairo_runner.vm.current_step, 0);
+        assert_eq!(cairo_runner.exec_scopes.data.len(), 1);
+        // Hints are compiled once and kept across pauses
+        let hint_data = cairo_runner.hints.as_ref().unwrap().data.as_ptr();
+        assert_matches!(
+            cairo_runner.run_until_next_hint(&mut hint_processor),
+            Ok(RunState::PausedAtHint(relocatable!(0, 0)))
+        );
+        assert_eq!(
+            cairo_runner.hints.as_ref().unwrap().data.as_ptr(),
+            hint_data
+        );
+        assert_eq!(cairo_runner.vm.current_step, 3);
+        assert_eq!(cairo_runner.exec_scopes.data.len(), 2);
+        assert_matches!(
+            cairo_runner.run_until_next_hint(&mut hint_processor),
+            Ok(RunState::Finished)
+        );
+        // Hints are dropped once the run is no longer paused
+        assert!(cairo_runner.hints.is_none());
+        assert_matches!(
+            cairo_runner.run_until_next_hint(&mut hint_processor),
+            Ok(RunState::Finished)
+        );
+
+        assert_eq!(cairo_runner.vm.get_pc(), end);
+        assert_eq!(
+            cairo_runner.vm.current_step,
+            expected_runner.vm.current_step
+        );
+        assert_eq!(cairo_runner.exec_scopes.data.len(), 1);
+        assert_eq!(cairo_runner.vm.trace, expected_runner.vm.trace);
+        assert_eq!(
+            cairo_runner.vm.segments.memory.data,
+            expected_runner.vm.segments.memory.data
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_until_next_hint_pauses_again_after_run_for_steps() {
+        let program = countdown_program(
+            Vec::new(),
+            vec![hint_params("vm_enter_scope()", HashMap::new())],
+        );
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        cairo_runner.initialize(false).unwrap();
+        assert_matches!(
+            cairo_runner.run_until_next_hint(&mut hint_processor),
+            Ok(RunState::PausedAtHint(relocatable!(0, 2)))
+        );
+        assert_eq!(cairo_runner.vm.current_step, 1);
+
+        // Resuming with run_for_steps executes the pending hint and leaves the pause
+        assert_matches!(
+            cairo_runner.run_for_steps(1, &mut hint_processor),
+            Ok(StepOutcome::ReachedLimit)
+        );
+        assert_eq!(cairo_runner.exec_scopes.data.len(), 2);
+        assert!(cairo_runner.hints.is_none());
+
+        // The loop reaches the same hint again, which must pause the run before executing it
+        assert_matches!(
+            cairo_runner.run_until_next_hint(&mut hint_processor),
+            Ok(RunState::PausedAtHint(relocatable!(0, 2)))
+        );
+        assert_eq!(cairo_runner.vm.current_step, 3);
+        assert_eq!(cairo_runner.exec_scopes.data.len(), 2);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    /*Program used: same as run_for_steps
+     */
+    fn snapshot_and_restore_vm_mid_run() {
+        let program = program!(
+            builtins = vec![BuiltinName::range_check],
+            data = vec_data!(
+                (4612671182993129469_i64),
+                (5189976364521848832_i64),
+                (18446744073709551615_i128),
+                (5199546496550207487_i64),
+                (4612389712311386111_i64),
+                (5198983563776393216_i64),
+                (2),
+                (2345108766317314046_i64),
+                (5191102247248822272_i64),
+                (5189976364521848832_i64),
+                (7),
+                (1226245742482522112_i64),
+                ((
+                    "3618502788666131213697322783095070105623107215331596699973092056135872020470",
+                    10
+                )),
+                (2345108766317314046_i64)
+            ),
+            main = Some(8),
+        );
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let end = cairo_runner.initialize(false).unwrap();
+        cairo_runner.run_for_steps(4, &mut hint_processor).unwrap();
+        let snapshot = cairo_runner.vm.snapshot();
+
+        cairo_runner.run_until_pc(end, &mut hint_processor).unwrap();
+        let expected_trace = cairo_runner.vm.trace.clone();
+        let expected_memory = cairo_runner.vm.segments.memory.data.clone();
+        let expected_step = cairo_runner.vm.current_step;
+
+        cairo_runner.vm.restore(snapshot);
+        assert_eq!(cairo_runner.vm.current_step, 4);
+        assert_eq!(cairo_runner.vm.trace.as_ref().map(|t| t.len()), Some(4));
+        assert_ne!(cairo_runner.vm.segments.memory.data, expected_memory);
+
+        cairo_runner.run_until_pc(end, &mut hint_processor).unwrap();
+        assert_eq!(cairo_runner.vm.get_pc(), end);
+        assert_eq!(cairo_runner.vm.current_step, expected_step);
+        assert_eq!(cairo_runner.vm.trace, expected_trace);
+        assert_eq!(cairo_runner.vm.segments.memory.data, expected_memory);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_empty() {
+        let program = program!();
+        let mut cairo_runner = cairo_runner!(&program, LayoutName::all_cairo, false, true);
+        assert_matches!(
+            cairo_runner.initialize(false),
+            Err(RunnerError::MissingMain)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     /*Program used:
     %builtins range_check
 
@@ -3526,35 +4367,50 @@ mod tests {
         cairo_runner.initialize_vm().unwrap();
 
         // Full takes 10 steps.
-        assert_matches!(cairo_runner.run_for_steps(1, &mut hint_processor), Ok(()));
+        assert_matches!(
+            cairo_runner.run_for_steps(1, &mut hint_processor),
+            Ok(StepOutcome::ReachedLimit)
+        );
         assert_matches!(
             cairo_runner.run_until_next_power_of_2(&mut hint_processor),
             Ok(())
         );
         assert_eq!(cairo_runner.vm.current_step, 1);
 
-        assert_matches!(cairo_runner.run_for_steps(1, &mut hint_processor), Ok(()));
+        assert_matches!(
+            cairo_runner.run_for_steps(1, &mut hint_processor),
+            Ok(StepOutcome::ReachedLimit)
+        );
         assert_matches!(
             cairo_runner.run_until_next_power_of_2(&mut hint_processor),
             Ok(())
         );
         assert_eq!(cairo_runner.vm.current_step, 2);
 
-        assert_matches!(cairo_runner.run_for_steps(1, &mut hint_processor), Ok(()));
+        assert_matches!(
+            cairo_runner.run_for_steps(1, &mut hint_processor),
+            Ok(StepOutcome::ReachedLimit)
+        );
         assert_matches!(
             cairo_runner.run_until_next_power_of_2(&mut hint_processor),
             Ok(())
         );
         assert_eq!(cairo_runner.vm.current_step, 4);
 
-        assert_matches!(cairo_runner.run_for_steps(1, &mut hint_processor), Ok(()));
+        assert_matches!(
+            cairo_runner.run_for_steps(1, &mut hint_processor),
+            Ok(StepOutcome::ReachedLimit)
+        );
         assert_matches!(
             cairo_runner.run_until_next_power_of_2(&mut hint_processor),
             Ok(())
         );
         assert_eq!(cairo_runner.vm.current_step, 8);
 
-        assert_matches!(cairo_runner.run_for_steps(1, &mut hint_processor), Ok(()));
+        assert_matches!(
+            cairo_runner.run_for_steps(1, &mut hint_processor),
+            Ok(StepOutcome::ReachedLimit)
+        );
         assert_matches!(
             cairo_runner.run_until_next_power_of_2(&mut hint_processor),
             Err(VirtualMachineError::EndOfProgram(6))
@@ -3574,6 +4430,264 @@ mod tests {
         assert_eq!(cairo_runner.get_constants(), &program_constants);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn precompile_all_hints() {
+        let program = program!(
+            data = vec_data!((2345108766317314046_i64), (2345108766317314046_i64)),
+            hints = BTreeMap::from([(0, vec![hint_params("vm_enter_scope()", HashMap::new())])]),
+        );
+        let cairo_runner = cairo_runner!(program);
+        let hint_processor = BuiltinHintProcessor::new_empty();
+        assert_matches!(cairo_runner.precompile_all_hints(&hint_processor), Ok(()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn load_data_read_by_hint() {
+        use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::HintFunc;
+        use crate::stdlib::rc::Rc;
+
+        // %{ preloaded_sum %}
+        // [ap] = 10, ap++
+        // ret
+        let program = program!(
+            data = vec_data!((0x480680017fff8000_i64), (10), (0x208b7fff7fff7ffe_i64)),
+            hints = BTreeMap::from([(
+                0,
+                vec![HintParams {
+                    code: "preloaded_sum".to_string(),
+                    accessible_scopes: vec![],
+                    flow_tracking_data: FlowTrackingData {
+                        ap_tracking: ApTracking::new(),
+                        reference_ids: HashMap::new(),
+                    },
+                }]
+            )]),
+            main = Some(0),
+        );
+        let mut cairo_runner = cairo_runner!(program);
+        let end = cairo_runner.initialize(false).unwrap();
+        let base = cairo_runner.vm.add_memory_segment();
+        assert_eq!(
+            cairo_runner.load_data(base, &[mayberelocatable!(5), mayberelocatable!(7)]),
+            Ok((base + 2_usize).unwrap())
+        );
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        hint_processor.add_hint(
+            "preloaded_sum".to_string(),
+            Rc::new(HintFunc(Box::new(move |vm, exec_scopes, _, _, _| {
+                let sum = vm
+                    .get_integer_range(base, 2)?
+                    .into_iter()
+                    .map(|value| *value)
+                    .sum::<Felt252>();
+                exec_scopes.insert_value("preloaded_sum", sum);
+                Ok(())
+            }))),
+        );
+        cairo_runner.run_until_pc(end, &mut hint_processor).unwrap();
+        assert_eq!(
+            cairo_runner
+                .exec_scopes
+                .get::<Felt252>("preloaded_sum")
+                .unwrap(),
+            Felt252::from(12)
+        );
+        assert_matches!(
+            cairo_runner.end_run(false, false, &mut hint_processor),
+            Ok(())
+        );
+        cairo_runner.vm.segments.compute_effective_sizes();
+        assert_matches!(verify_secure_runner(&cairo_runner, true, None), Ok(()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compiles_hints_with_each_hint_processor() {
+        use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::{
+            hint_code_hash, HintFunc,
+        };
+        use crate::stdlib::rc::Rc;
+
+        let hint_code = "enter_scope_by_hash()";
+        let program = countdown_program(Vec::new(), vec![hint_params(hint_code, HashMap::new())]);
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let end = cairo_runner.initialize(false).unwrap();
+        // The loop hint is not reached yet
+        assert_matches!(
+            cairo_runner.run_for_steps(1, &mut BuiltinHintProcessor::new_empty()),
+            Ok(StepOutcome::ReachedLimit)
+        );
+
+        // The hint is only known to the second hint processor, which resolves it when compiling it
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        hint_processor.add_hint_by_hash(
+            hint_code_hash(hint_code),
+            Rc::new(HintFunc(Box::new(|_, exec_scopes, _, _, _| {
+                exec_scopes.enter_scope(HashMap::new());
+                Ok(())
+            }))),
+        );
+        assert_matches!(cairo_runner.run_until_pc(end, &mut hint_processor), Ok(()));
+        assert_eq!(cairo_runner.exec_scopes.data.len(), 11);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn new_from_prepared_reuses_compiled_hints() {
+        let program = countdown_program(
+            Vec::new(),
+            vec![hint_params("vm_enter_scope()", HashMap::new())],
+        );
+        let run = |cairo_runner: &mut CairoRunner| {
+            let end = cairo_runner.initialize(false).unwrap();
+            cairo_runner
+                .run_until_pc(end, &mut BuiltinHintProcessor::new_empty())
+                .unwrap();
+            cairo_runner.relocate(true).unwrap();
+            (
+                cairo_runner.relocated_memory.clone(),
+                cairo_runner.relocated_trace.clone(),
+                cairo_runner.exec_scopes.data.len(),
+            )
+        };
+
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let expected = run(&mut cairo_runner);
+        assert_eq!(expected.2, 11);
+
+        let mut compiled_hints =
+            CairoRunner::compile_hints(&program, &BuiltinHintProcessor::new_empty()).unwrap();
+        assert_eq!(compiled_hints.len(), 1);
+        for _ in 0..2 {
+            let mut cairo_runner = CairoRunner::new_from_prepared(
+                &program,
+                compiled_hints,
+                LayoutName::all_cairo,
+                None,
+                false,
+                true,
+            )
+            .unwrap();
+            assert_eq!(run(&mut cairo_runner), expected);
+            compiled_hints = cairo_runner.take_compiled_hints();
+            assert_eq!(compiled_hints.len(), 1);
+        }
+
+        assert_matches!(
+            CairoRunner::new_from_prepared(
+                &program,
+                HashMap::new(),
+                LayoutName::all_cairo,
+                None,
+                false,
+                true,
+            )
+            .err(),
+            Some(RunnerError::MissingCompiledHint(0))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_program_only_fixtures() {
+        // A program without data fails when it is loaded, before it can be validated
+        let no_data_program =
+            include_bytes!("../../../../cairo_programs/manually_compiled/no_data_program.json");
+        assert_matches!(Program::from_bytes(no_data_program, Some("main")), Err(_));
+
+        let no_main_program =
+            include_bytes!("../../../../cairo_programs/manually_compiled/no_main_program.json");
+        let program = Program::from_bytes(no_main_program, None).unwrap();
+        let cairo_runner = cairo_runner!(program);
+        assert_matches!(
+            cairo_runner.validate_program_only(),
+            Err(RunnerError::MissingMain)
+        );
+
+        let valid_program =
+            include_bytes!("../../../../cairo_programs/manually_compiled/valid_program_a.json");
+        let program = Program::from_bytes(valid_program, Some("main")).unwrap();
+        let cairo_runner = cairo_runner!(program);
+        assert_matches!(cairo_runner.validate_program_only(), Ok(()));
+        // Validation doesn't initialize the runner itself
+        assert_eq!(cairo_runner.vm.segments.num_segments(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_program_only_invalid_programs() {
+        let program = program!(
+            builtins = vec![BuiltinName::bitwise],
+            data = vec_data!((2345108766317314046_i64)),
+            main = Some(0),
+        );
+        let cairo_runner = cairo_runner!(program, LayoutName::plain);
+        assert_matches!(
+            cairo_runner.validate_program_only(),
+            Err(RunnerError::BuiltinNotInLayout(bx))
+                if *bx == (vec![BuiltinName::bitwise], LayoutName::plain)
+        );
+
+        let program = program!(data = vec_data!((2345108766317314046_i64)), main = Some(1),);
+        let cairo_runner = cairo_runner!(program);
+        assert_eq!(
+            cairo_runner.validate_program_only(),
+            Err(RunnerError::EntrypointOutOfBounds(Box::new((1, 1))))
+        );
+
+        let program = program!(
+            data = vec_data!((2345108766317314046_i64)),
+            hints = BTreeMap::from([(
+                0,
+                vec![HintParams {
+                    code: "memory[ap] = to_felt_or_relocatable(ids.a)".to_string(),
+                    accessible_scopes: vec![],
+                    flow_tracking_data: FlowTrackingData {
+                        ap_tracking: ApTracking::new(),
+                        reference_ids: HashMap::from([("main.a".to_string(), 3)]),
+                    },
+                }]
+            )]),
+            main = Some(0),
+        );
+        let cairo_runner = cairo_runner!(program);
+        assert_eq!(
+            cairo_runner.validate_program_only(),
+            Err(RunnerError::MissingHintReference(Box::new((
+                "main.a".to_string(),
+                3
+            ))))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn precompile_all_hints_malformed_hint() {
+        // The second hint refers to a reference that doesn't exist in the program
+        let program = program!(
+            data = vec_data!((2345108766317314046_i64), (2345108766317314046_i64)),
+            hints = BTreeMap::from([
+                (0, vec![hint_params("vm_enter_scope()", HashMap::new())]),
+                (
+                    1,
+                    vec![hint_params(
+                        "memory[ap] = to_felt_or_relocatable(ids.a)",
+                        HashMap::from([("main.a".to_string(), 3)])
+                    )]
+                ),
+            ]),
+        );
+        let cairo_runner = cairo_runner!(program);
+        let hint_processor = BuiltinHintProcessor::new_empty();
+        assert_matches!(
+            cairo_runner.precompile_all_hints(&hint_processor),
+            Err(VirtualMachineError::CompileHintFail(code)) if code.as_ref() == "memory[ap] = to_felt_or_relocatable(ids.a)"
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_memory_holes_missing_segment_used_sizes() {
@@ -3855,6 +4969,102 @@ mod tests {
         assert_eq!(runner.get_execution_resources().unwrap().n_steps, 80);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn final_state_fibonacci() {
+        let program_data = include_bytes!("../../../../cairo_programs/fibonacci.json");
+        let program = Program::from_bytes(program_data, Some("main")).unwrap();
+        let mut cairo_runner = cairo_runner!(program);
+        let end = cairo_runner.initialize(false).unwrap();
+        cairo_runner
+            .run_until_pc(end, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        assert_eq!(cairo_runner.final_state().0, end);
+
+        let program_data =
+            include_bytes!("../../../../cairo_programs/proof_programs/fibonacci.json");
+        let cairo_run_config = CairoRunConfig {
+            layout: LayoutName::all_cairo,
+            proof_mode: true,
+            ..Default::default()
+        };
+        let mut hint_executor = BuiltinHintProcessor::new_empty();
+        let runner = cairo_run(program_data, &cairo_run_config, &mut hint_executor).unwrap();
+        let end = runner.program_base.unwrap() + runner.program.shared_program_data.end.unwrap();
+        assert_eq!(runner.final_state().0, end.unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn unrelocate_address_round_trip() {
+        let program = countdown_program(
+            vec![BuiltinName::output, BuiltinName::range_check],
+            Vec::new(),
+        );
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let end = cairo_runner.initialize(false).unwrap();
+        assert_eq!(cairo_runner.unrelocate_address(1), None);
+        cairo_runner
+            .run_until_pc(end, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        cairo_runner.relocate(true).unwrap();
+
+        let relocation_table = cairo_runner.vm.relocation_table.clone().unwrap();
+        let mut n_addresses = 0;
+        for (segment_index, start) in relocation_table.iter().enumerate() {
+            let size = cairo_runner.vm.get_segment_size(segment_index).unwrap();
+            for offset in 0..size {
+                let addr = Relocatable::from((segment_index as isize, offset));
+                assert_eq!(cairo_runner.unrelocate_address(start + offset), Some(addr));
+                n_addresses += 1;
+            }
+        }
+        // The builtin segments are empty, so every relocated address maps back to a segment
+        assert_eq!(n_addresses + 1, cairo_runner.relocated_memory.len());
+        assert_eq!(cairo_runner.unrelocate_address(0), None);
+        assert_eq!(
+            cairo_runner.unrelocate_address(cairo_runner.relocated_memory.len()),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn final_state_loop() {
+        let program = countdown_program(Vec::new(), Vec::new());
+        let mut cairo_runner = cairo_runner!(program);
+        let end = cairo_runner.initialize(false).unwrap();
+        cairo_runner
+            .run_until_pc(end, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        // The initial 10 and one cell per iteration down to 0 are pushed after the return fp and
+        // pc, and `ret` restores the return fp
+        assert_eq!(
+            cairo_runner.final_state(),
+            (
+                Relocatable::from((3, 0)),
+                Relocatable::from((1, 13)),
+                Relocatable::from((1, 0))
+            )
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_execution_resources_matches_cairo_pie() {
+        let program_data = include_bytes!("../../../../cairo_programs/fibonacci.json");
+        let cairo_run_config = CairoRunConfig {
+            layout: LayoutName::all_cairo,
+            ..Default::default()
+        };
+        let mut hint_executor = BuiltinHintProcessor::new_empty();
+        let runner = cairo_run(program_data, &cairo_run_config, &mut hint_executor).unwrap();
+        assert_eq!(
+            runner.get_execution_resources().unwrap(),
+            runner.get_cairo_pie().unwrap().execution_resources
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_execution_resources_run_program_no_trace() {
@@ -4021,6 +5231,47 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_public_memory_proof_mode() {
+        let program = proof_mode_loop_program();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::plain, true);
+        let end = cairo_runner.initialize(false).unwrap();
+        cairo_runner
+            .run_until_pc(end, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        cairo_runner
+            .end_run(false, false, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        assert_eq!(
+            cairo_runner.get_public_memory(),
+            Err(RunnerError::PublicMemoryNoFinalizeSegments)
+        );
+
+        cairo_runner.finalize_segments().unwrap();
+        cairo_runner.relocate(true).unwrap();
+        let public_memory = cairo_runner.get_public_memory().unwrap();
+        for (i, value) in program.shared_program_data.data.iter().enumerate() {
+            assert_eq!(public_memory[i], (1 + i, *value.get_int_ref().unwrap()));
+        }
+        // The execution stack prefix follows the program segment
+        let exec_base = cairo_runner.execution_base.unwrap();
+        assert_eq!(
+            public_memory.len(),
+            program.shared_program_data.data.len()
+                + cairo_runner.execution_public_memory.as_ref().unwrap().len()
+        );
+        assert!(public_memory.iter().any(|(addr, _)| *addr
+            == relocate_address(
+                exec_base,
+                cairo_runner.vm.relocation_table.as_ref().unwrap()
+            )
+            .unwrap()));
+        for (addr, value) in public_memory {
+            assert_eq!(cairo_runner.relocated_memory[addr], Some(value));
+        }
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn finalize_segments_run_ended_not_emptyproof_mode_with_execution_public_memory() {
@@ -4379,6 +5630,43 @@ mod tests {
         assert_eq!(cairo_runner.vm.segments.num_segments(), 4);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn initialize_function_runner_with_segment_arena_max_segments() {
+        let program = program!();
+
+        let mut cairo_runner = cairo_runner!(program);
+        cairo_runner.set_segment_arena_max_segments(Some(1));
+        cairo_runner
+            .initialize_function_runner_cairo_1(&[BuiltinName::segment_arena])
+            .unwrap();
+        for builtin in cairo_runner.vm.builtin_runners.iter() {
+            builtin.add_validation_rule(&mut cairo_runner.vm.segments.memory);
+        }
+
+        // The arena's first instance starts right after its initial (infos, n_segments, n_finalized)
+        let arena = cairo_runner.vm.builtin_runners[0].base() as isize;
+        assert_eq!(
+            cairo_runner
+                .vm
+                .segments
+                .memory
+                .insert_value(Relocatable::from((arena, 4)), 1),
+            Ok(())
+        );
+        assert_eq!(
+            cairo_runner
+                .vm
+                .segments
+                .memory
+                .insert_value(Relocatable::from((arena, 7)), 2),
+            Err(MemoryError::SegmentArenaLimitExceeded(Box::new((
+                Felt252::from(2),
+                1
+            ))))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn initialize_segments_incorrect_layout_plain_one_builtin() {
@@ -4386,13 +5674,70 @@ mod tests {
         let mut cairo_runner = cairo_runner!(program, LayoutName::plain);
         assert_eq!(
             cairo_runner.initialize_builtins(false),
-            Err(RunnerError::NoBuiltinForInstance(Box::new((
-                HashSet::from([BuiltinName::output]),
+            Err(RunnerError::BuiltinNotInLayout(Box::new((
+                vec![BuiltinName::output],
+                LayoutName::plain
+            ))))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn initialize_ec_op_program_plain_layout() {
+        let program = program![BuiltinName::ec_op];
+        let mut cairo_runner = cairo_runner!(program, LayoutName::plain);
+        let err = cairo_runner.initialize(false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Builtins [ec_op] are not present in layout plain"
+        );
+        assert_eq!(
+            err,
+            RunnerError::BuiltinNotInLayout(Box::new((
+                vec![BuiltinName::ec_op],
+                LayoutName::plain
+            )))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn initialize_program_with_builtins_missing_from_layout() {
+        let program = program![
+            BuiltinName::output,
+            BuiltinName::pedersen,
+            BuiltinName::range_check,
+            BuiltinName::bitwise
+        ];
+        let mut cairo_runner = cairo_runner!(program, LayoutName::small);
+        assert_eq!(
+            cairo_runner.initialize(false),
+            Err(RunnerError::BuiltinNotInLayout(Box::new((
+                vec![BuiltinName::bitwise],
+                LayoutName::small
+            ))))
+        );
+
+        let program = program![BuiltinName::output, BuiltinName::pedersen];
+        let mut cairo_runner = cairo_runner!(program, LayoutName::plain);
+        assert_eq!(
+            cairo_runner.initialize(false),
+            Err(RunnerError::BuiltinNotInLayout(Box::new((
+                vec![BuiltinName::output, BuiltinName::pedersen],
                 LayoutName::plain
             ))))
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn initialize_builtins_ec_op_program_plain_layout_allow_missing() {
+        let program = program![BuiltinName::ec_op];
+        let mut cairo_runner = cairo_runner!(program, LayoutName::plain);
+        assert_matches!(cairo_runner.initialize_builtins(true), Ok(()));
+        assert!(cairo_runner.vm.builtin_runners.is_empty());
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn initialize_segments_incorrect_layout_plain_two_builtins() {
@@ -4400,8 +5745,8 @@ mod tests {
         let mut cairo_runner = cairo_runner!(program, LayoutName::plain);
         assert_eq!(
             cairo_runner.initialize_builtins(false),
-            Err(RunnerError::NoBuiltinForInstance(Box::new((
-                HashSet::from([BuiltinName::output, BuiltinName::pedersen]),
+            Err(RunnerError::BuiltinNotInLayout(Box::new((
+                vec![BuiltinName::output, BuiltinName::pedersen],
                 LayoutName::plain
             ))))
         );
@@ -4414,8 +5759,8 @@ mod tests {
         let mut cairo_runner = cairo_runner!(program, LayoutName::small);
         assert_eq!(
             cairo_runner.initialize_builtins(false),
-            Err(RunnerError::NoBuiltinForInstance(Box::new((
-                HashSet::from([BuiltinName::bitwise]),
+            Err(RunnerError::BuiltinNotInLayout(Box::new((
+                vec![BuiltinName::bitwise],
                 LayoutName::small,
             ))))
         );
@@ -5355,6 +6700,65 @@ mod tests {
         runner.vm.segments.segment_sizes = HashMap::from([(0, 0), (1, 2), (2, 0), (3, 0)]);
     }
 
+    #[test]
+    fn get_cairo_pie_extra_segments() {
+        let program = program!(main = Some(0),);
+        let mut runner = cairo_runner!(program);
+        runner.program_base = Some(Relocatable::from((0, 0)));
+        runner.execution_base = Some(Relocatable::from((1, 0)));
+        runner.vm.segments = segments![
+            // return_fp
+            ((1, 0), (2, 0)),
+            // return_pc
+            ((1, 1), (3, 0)),
+            ((4, 0), 1),
+            ((4, 1), 2),
+            ((5, 0), 3)
+        ];
+        runner.vm.segments.compute_effective_sizes();
+
+        let cairo_pie = runner.get_cairo_pie().unwrap();
+        assert_eq!(
+            cairo_pie.extra_segments(),
+            &[
+                cairo_pie::SegmentInfo::from((4, 2)),
+                cairo_pie::SegmentInfo::from((5, 1))
+            ]
+        );
+    }
+
+    #[test]
+    fn program_builtins_match_cairo_pie() {
+        let program = program!(main = Some(0),);
+        let mut runner = cairo_runner!(program);
+        runner.program_base = Some(Relocatable::from((0, 0)));
+        runner.execution_base = Some(Relocatable::from((1, 0)));
+        runner.vm.segments = segments![
+            // return_fp
+            ((1, 0), (2, 0)),
+            // return_pc
+            ((1, 1), (3, 0))
+        ];
+        // return_fp and return_pc segments
+        runner.vm.segments.add();
+        runner.vm.segments.add();
+        runner.vm.segments.compute_effective_sizes();
+
+        let mut cairo_pie = runner.get_cairo_pie().unwrap();
+        cairo_pie.metadata.program.builtins = vec![BuiltinName::output, BuiltinName::pedersen];
+        assert_eq!(
+            cairo_pie.builtins(),
+            &[BuiltinName::output, BuiltinName::pedersen]
+        );
+
+        let matching_program = program![BuiltinName::output, BuiltinName::pedersen];
+        assert!(matching_program.builtins_match(&cairo_pie));
+        let reordered_program = program![BuiltinName::pedersen, BuiltinName::output];
+        assert!(!reordered_program.builtins_match(&cairo_pie));
+        let mismatching_program = program![BuiltinName::output, BuiltinName::range_check];
+        assert!(!mismatching_program.builtins_match(&cairo_pie));
+    }
+
     #[test]
     fn get_air_private_input() {
         let program_content =
@@ -1,3 +1,5 @@
+pub mod replay;
+
 pub mod trace_entry {
     use serde::{Deserialize, Serialize};
 
@@ -39,4 +41,103 @@ pub mod trace_entry {
         }
         Ok(relocation_table[segment_index] + value.offset)
     }
+
+    /// Relocates a trace using the given relocation table, turning relocatable registers into numbered ones.
+    /// ap and fp are relocated as offsets into the execution segment (segment 1).
+    pub fn relocate_trace(
+        trace: &[TraceEntry],
+        relocation_table: &[usize],
+    ) -> Result<Vec<RelocatedTraceEntry>, TraceError> {
+        let segment_1_base = relocation_table
+            .get(1)
+            .ok_or(TraceError::NoRelocationFound)?;
+
+        trace
+            .iter()
+            .map(|entry| {
+                Ok(RelocatedTraceEntry {
+                    pc: relocate_trace_register(entry.pc, relocation_table)?,
+                    ap: entry.ap + segment_1_base,
+                    fp: entry.fp + segment_1_base,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[cfg(target_arch = "wasm32")]
+        use wasm_bindgen_test::*;
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        // Test to catch possible trace entry size regressions
+        fn test_trace_entry_size() {
+            let size = crate::stdlib::mem::size_of::<TraceEntry>();
+            assert!(size <= 4 * crate::stdlib::mem::size_of::<usize>(), "{size}")
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn relocate_trace_synthetic() {
+            let trace = vec![
+                TraceEntry {
+                    pc: Relocatable::from((0, 0)),
+                    ap: 3,
+                    fp: 3,
+                },
+                TraceEntry {
+                    pc: Relocatable::from((0, 2)),
+                    ap: 5,
+                    fp: 3,
+                },
+                TraceEntry {
+                    pc: Relocatable::from((2, 1)),
+                    ap: 6,
+                    fp: 6,
+                },
+            ];
+            let relocation_table = [1, 5, 20];
+            assert_eq!(
+                relocate_trace(&trace, &relocation_table).unwrap(),
+                vec![
+                    RelocatedTraceEntry {
+                        pc: 1,
+                        ap: 8,
+                        fp: 8
+                    },
+                    RelocatedTraceEntry {
+                        pc: 3,
+                        ap: 10,
+                        fp: 8
+                    },
+                    RelocatedTraceEntry {
+                        pc: 21,
+                        ap: 11,
+                        fp: 11
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn relocate_trace_missing_segment() {
+            let trace = vec![TraceEntry {
+                pc: Relocatable::from((3, 0)),
+                ap: 0,
+                fp: 0,
+            }];
+            assert_eq!(
+                relocate_trace(&trace, &[1, 5]),
+                Err(TraceError::NoRelocationFound)
+            );
+            assert_eq!(
+                relocate_trace(&[], &[1]),
+                Err(TraceError::NoRelocationFound)
+            );
+        }
+    }
 }
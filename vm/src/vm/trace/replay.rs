@@ -0,0 +1,282 @@
+use num_traits::ToPrimitive;
+
+use crate::{
+    stdlib::prelude::*,
+    types::{
+        instruction::{ApUpdate, FpUpdate, Instruction, Op1Addr, Opcode, PcUpdate, Register, Res},
+        program::Program,
+        relocatable::MaybeRelocatable,
+    },
+    vm::{
+        decoding::decoder::decode_instruction, errors::trace_errors::ReplayError,
+        trace::trace_entry::RelocatedTraceEntry,
+    },
+    Felt252,
+};
+
+/// Address at which the program segment starts in the relocated memory.
+const PROGRAM_BASE: usize = 1;
+
+/// Checks that a relocated trace and memory obtained from a previous run are consistent with each
+/// other and with the program, without running any hints.
+///
+/// For every trace entry, the instruction at pc is decoded and its operands are read from
+/// `memory`, which must hold the values the instruction asserted or wrote. The registers of the
+/// following entry must match the ones implied by the instruction.
+pub fn replay_verify(
+    program: &Program,
+    trace: &[RelocatedTraceEntry],
+    memory: &[Option<Felt252>],
+) -> Result<(), ReplayError> {
+    for (i, value) in program.shared_program_data.data.iter().enumerate() {
+        // Relocatable values depend on the segment layout of the run and can't be checked here
+        if let MaybeRelocatable::Int(expected) = value {
+            let found = memory.get(PROGRAM_BASE + i).copied().flatten();
+            if found != Some(*expected) {
+                return Err(ReplayError::ProgramMismatch(Box::new((
+                    PROGRAM_BASE + i,
+                    *expected,
+                    found,
+                ))));
+            }
+        }
+    }
+
+    for (step, entry) in trace.iter().enumerate() {
+        let next = replay_step(step, entry, memory)?;
+        if let Some(next_entry) = trace.get(step + 1) {
+            for (register, expected, found) in [
+                ("pc", next.pc, next_entry.pc),
+                ("ap", next.ap, next_entry.ap),
+                ("fp", next.fp, next_entry.fp),
+            ] {
+                if expected != Felt252::from(found) {
+                    return Err(ReplayError::RegisterMismatch(Box::new((
+                        step,
+                        register,
+                        expected,
+                        Felt252::from(found),
+                    ))));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Register values implied by an instruction for the step after it.
+struct NextRegisters {
+    pc: Felt252,
+    ap: Felt252,
+    fp: Felt252,
+}
+
+fn replay_step(
+    step: usize,
+    entry: &RelocatedTraceEntry,
+    memory: &[Option<Felt252>],
+) -> Result<NextRegisters, ReplayError> {
+    let get = |address: usize| {
+        memory
+            .get(address)
+            .copied()
+            .flatten()
+            .ok_or_else(|| ReplayError::UnknownMemoryCell(Box::new((step, address))))
+    };
+    let to_address = |value: Felt252| {
+        value
+            .to_usize()
+            .ok_or_else(|| ReplayError::InvalidAddress(Box::new((step, value))))
+    };
+    let offset =
+        |base: usize, offset: isize| to_address(Felt252::from(base as i128 + offset as i128));
+
+    let instruction: Instruction = get(entry.pc)?
+        .to_u64()
+        .and_then(|encoded| decode_instruction(encoded).ok())
+        .ok_or_else(|| ReplayError::InvalidInstruction(Box::new((step, entry.pc))))?;
+    let register = |register: Register| match register {
+        Register::AP => entry.ap,
+        Register::FP => entry.fp,
+    };
+
+    let dst_addr = offset(register(instruction.dst_register), instruction.off0)?;
+    let op0_addr = offset(register(instruction.op0_register), instruction.off1)?;
+    let dst = get(dst_addr)?;
+    let op0 = get(op0_addr)?;
+    let op1_base = match instruction.op1_addr {
+        Op1Addr::Imm => entry.pc,
+        Op1Addr::AP => entry.ap,
+        Op1Addr::FP => entry.fp,
+        Op1Addr::Op0 => to_address(op0)?,
+    };
+    let op1 = get(offset(op1_base, instruction.off2)?)?;
+
+    let res = match instruction.res {
+        Res::Op1 => Some(op1),
+        Res::Add => Some(op0 + op1),
+        Res::Mul => Some(op0 * op1),
+        Res::Unconstrained => None,
+    };
+    let size = Felt252::from(instruction.size());
+    let pc = Felt252::from(entry.pc);
+    let ap = Felt252::from(entry.ap);
+    let fp = Felt252::from(entry.fp);
+
+    match instruction.opcode {
+        Opcode::AssertEq => {
+            let res = res.ok_or(ReplayError::UnconstrainedRes(step))?;
+            if dst != res {
+                return Err(ReplayError::DiffAssertValues(Box::new((step, dst, res))));
+            }
+        }
+        Opcode::Call => {
+            if op0 != pc + size {
+                return Err(ReplayError::CallMismatch(Box::new((
+                    step,
+                    "return pc",
+                    pc + size,
+                    op0,
+                ))));
+            }
+            if dst != fp {
+                return Err(ReplayError::CallMismatch(Box::new((
+                    step,
+                    "return fp",
+                    fp,
+                    dst,
+                ))));
+            }
+        }
+        Opcode::NOp | Opcode::Ret => {}
+    }
+
+    let next_pc = match instruction.pc_update {
+        PcUpdate::Regular => pc + size,
+        PcUpdate::Jump => res.ok_or(ReplayError::UnconstrainedRes(step))?,
+        PcUpdate::JumpRel => pc + res.ok_or(ReplayError::UnconstrainedRes(step))?,
+        PcUpdate::Jnz if dst == Felt252::ZERO => pc + size,
+        PcUpdate::Jnz => pc + op1,
+    };
+    let next_ap = match instruction.ap_update {
+        ApUpdate::Regular => ap,
+        ApUpdate::Add => ap + res.ok_or(ReplayError::UnconstrainedRes(step))?,
+        ApUpdate::Add1 => ap + Felt252::ONE,
+        ApUpdate::Add2 => ap + Felt252::TWO,
+    };
+    let next_fp = match instruction.fp_update {
+        FpUpdate::Regular => fp,
+        FpUpdate::APPlus2 => ap + Felt252::TWO,
+        FpUpdate::Dst => dst,
+    };
+    Ok(NextRegisters {
+        pc: next_pc,
+        ap: next_ap,
+        fp: next_fp,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cairo_run::{cairo_run, CairoRunConfig};
+    use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
+    use crate::types::builtin_name::BuiltinName;
+    use crate::types::layout_name::LayoutName;
+    use crate::utils::test_utils::*;
+    use assert_matches::assert_matches;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn replay_verify_fibonacci() {
+        let program_content = include_bytes!("../../../../cairo_programs/fibonacci.json");
+        let cairo_run_config = CairoRunConfig {
+            trace_enabled: true,
+            relocate_mem: true,
+            ..Default::default()
+        };
+        let runner = cairo_run(
+            program_content,
+            &cairo_run_config,
+            &mut BuiltinHintProcessor::new_empty(),
+        )
+        .unwrap();
+        let trace = runner.relocated_trace.as_ref().unwrap();
+        let mut memory = runner.relocated_memory.clone();
+        assert_eq!(replay_verify(&runner.program, trace, &memory), Ok(()));
+
+        // Corrupt a cell written by the run
+        let last = trace.last().unwrap();
+        memory[last.ap - 1] = memory[last.ap - 1].map(|value| value + Felt252::ONE);
+        assert_matches!(replay_verify(&runner.program, trace, &memory), Err(_));
+
+        // Drop a cell written by the run
+        memory[last.ap - 1] = None;
+        assert_matches!(
+            replay_verify(&runner.program, trace, &memory),
+            Err(ReplayError::UnknownMemoryCell(_))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn replay_verify_call_and_return() {
+        let program = program!(
+            builtins = vec![BuiltinName::output],
+            data = vec_data!(
+                (4612671182993129469_i64),
+                (5198983563776393216_i64),
+                (1),
+                (2345108766317314046_i64),
+                (5191102247248822272_i64),
+                (5189976364521848832_i64),
+                (1),
+                (1226245742482522112_i64),
+                ((
+                    "3618502788666131213697322783095070105623107215331596699973092056135872020474",
+                    10
+                )),
+                (5189976364521848832_i64),
+                (17),
+                (1226245742482522112_i64),
+                ((
+                    "3618502788666131213697322783095070105623107215331596699973092056135872020470",
+                    10
+                )),
+                (2345108766317314046_i64)
+            ),
+            main = Some(4),
+        );
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let end = cairo_runner.initialize(false).unwrap();
+        cairo_runner
+            .run_until_pc(end, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        cairo_runner.relocate(true).unwrap();
+        let trace = cairo_runner.relocated_trace.as_ref().unwrap();
+        let mut memory = cairo_runner.relocated_memory.clone();
+        assert_eq!(replay_verify(&program, trace, &memory), Ok(()));
+
+        // The call at pc 8 writes the return fp at ap 20
+        memory[20] = Some(Felt252::from(17));
+        assert_eq!(
+            replay_verify(&program, trace, &memory),
+            Err(ReplayError::CallMismatch(Box::new((
+                2,
+                "return fp",
+                Felt252::from(18),
+                Felt252::from(17)
+            ))))
+        );
+
+        // Program data must be found at the start of the relocated memory
+        memory[1] = None;
+        assert_matches!(
+            replay_verify(&program, trace, &memory),
+            Err(ReplayError::ProgramMismatch(_))
+        );
+    }
+}
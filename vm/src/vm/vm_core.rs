@@ -24,7 +24,10 @@ use crate::{
             BuiltinRunner, OutputBuiltinRunner, RangeCheckBuiltinRunner, SignatureBuiltinRunner,
         },
         trace::trace_entry::TraceEntry,
-        vm_memory::memory_segments::MemorySegmentManager,
+        vm_memory::{
+            memory::{AddressSet, MemoryCell},
+            memory_segments::MemorySegmentManager,
+        },
     },
 };
 
@@ -93,6 +96,54 @@ pub struct VirtualMachine {
     #[cfg(feature = "test_utils")]
     pub(crate) hooks: crate::vm::hooks::Hooks,
     pub(crate) relocation_table: Option<Vec<usize>>,
+    #[cfg(feature = "profile_builtins")]
+    builtin_timings:
+        Option<crate::stdlib::cell::RefCell<HashMap<BuiltinName, std::time::Duration>>>,
+}
+
+/// Checkpoint of the execution state of a [`VirtualMachine`].
+/// Created by [`VirtualMachine::snapshot`] and consumed by [`VirtualMachine::restore`].
+/// The trace is only tracked by its length, as execution can only append entries to it.
+/// Memory is copied in full, so each snapshot costs time and space linear in the VM's memory.
+#[derive(Clone)]
+pub struct VmSnapshot {
+    pc: Relocatable,
+    ap: usize,
+    fp: usize,
+    current_step: usize,
+    trace_len: Option<usize>,
+    rc_limits: Option<(isize, isize)>,
+    skip_instruction_execution: bool,
+    run_finished: bool,
+    data: Vec<Vec<MemoryCell>>,
+    temp_data: Vec<Vec<MemoryCell>>,
+    relocation_rules: HashMap<usize, Relocatable>,
+    validated_addresses: AddressSet,
+    segment_sizes: HashMap<usize, usize>,
+    segment_used_sizes: Option<Vec<usize>>,
+    public_memory_offsets: HashMap<usize, Vec<(usize, usize)>>,
+    zero_segment_index: usize,
+    zero_segment_size: usize,
+    builtin_runners: Vec<BuiltinRunner>,
+}
+
+/// A memory cell of a builtin segment whose value differs from the one deduced by the builtin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoDeductionMismatch {
+    pub builtin: BuiltinName,
+    pub address: Relocatable,
+    pub expected: MaybeRelocatable,
+    pub found: Option<MaybeRelocatable>,
+}
+
+impl From<AutoDeductionMismatch> for VirtualMachineError {
+    fn from(mismatch: AutoDeductionMismatch) -> Self {
+        VirtualMachineError::InconsistentAutoDeduction(Box::new((
+            mismatch.builtin,
+            mismatch.expected,
+            mismatch.found,
+        )))
+    }
 }
 
 impl VirtualMachine {
@@ -122,6 +173,8 @@ impl VirtualMachine {
             #[cfg(feature = "test_utils")]
             hooks: Default::default(),
             relocation_table: None,
+            #[cfg(feature = "profile_builtins")]
+            builtin_timings: None,
         }
     }
 
@@ -293,7 +346,14 @@ impl VirtualMachine {
     ) -> Result<Option<MaybeRelocatable>, VirtualMachineError> {
         for builtin in self.builtin_runners.iter() {
             if builtin.base() as isize == address.segment_index {
-                match builtin.deduce_memory_cell(address, &self.segments.memory) {
+                #[cfg(feature = "profile_builtins")]
+                let start = std::time::Instant::now();
+                let deduced = builtin.deduce_memory_cell(address, &self.segments.memory);
+                #[cfg(feature = "profile_builtins")]
+                if let Some(timings) = &self.builtin_timings {
+                    *timings.borrow_mut().entry(builtin.name()).or_default() += start.elapsed();
+                }
+                match deduced {
                     Ok(maybe_reloc) => return Ok(maybe_reloc),
                     Err(error) => return Err(VirtualMachineError::RunnerError(error)),
                 };
@@ -302,6 +362,22 @@ impl VirtualMachine {
         Ok(None)
     }
 
+    /// Starts recording the time spent by each builtin deducing memory cells.
+    #[cfg(feature = "profile_builtins")]
+    pub fn enable_builtin_profiling(&mut self) {
+        self.builtin_timings = Some(Default::default());
+    }
+
+    /// Returns the time spent by each builtin deducing memory cells since profiling was enabled.
+    /// The map is empty if profiling was never enabled.
+    #[cfg(feature = "profile_builtins")]
+    pub fn builtin_timings(&self) -> HashMap<BuiltinName, std::time::Duration> {
+        self.builtin_timings
+            .as_ref()
+            .map(|timings| timings.borrow().clone())
+            .unwrap_or_default()
+    }
+
     ///Computes the value of res if possible
     fn compute_res(
         &self,
@@ -685,23 +761,73 @@ impl VirtualMachine {
 
     ///Makes sure that all assigned memory cells are consistent with their auto deduction rules.
     pub fn verify_auto_deductions(&self) -> Result<(), VirtualMachineError> {
+        match self.collect_auto_deduction_mismatches(false)?.pop() {
+            Some(mismatch) => Err(mismatch.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks all assigned memory cells against their auto deduction rules, returning the mismatches found.
+    /// If `collect_all_deduction_mismatches` is false, the search stops at the first mismatch.
+    pub fn collect_auto_deduction_mismatches(
+        &self,
+        collect_all_deduction_mismatches: bool,
+    ) -> Result<Vec<AutoDeductionMismatch>, VirtualMachineError> {
+        let mut mismatches = Vec::new();
         for builtin in self.builtin_runners.iter() {
-            let index: usize = builtin.base();
-            for (offset, value) in self.segments.memory.data[index].iter().enumerate() {
-                if let Some(deduced_memory_cell) = builtin
-                    .deduce_memory_cell(
-                        Relocatable::from((index as isize, offset)),
-                        &self.segments.memory,
-                    )
-                    .map_err(VirtualMachineError::RunnerError)?
-                {
-                    let value = value.get_value();
-                    if Some(&deduced_memory_cell) != value.as_ref() && value.is_some() {
-                        return Err(VirtualMachineError::InconsistentAutoDeduction(Box::new((
-                            builtin.name(),
-                            deduced_memory_cell,
-                            value,
-                        ))));
+            self.builtin_auto_deduction_mismatches(
+                builtin,
+                collect_all_deduction_mismatches,
+                &mut mismatches,
+            )?;
+            if !collect_all_deduction_mismatches && !mismatches.is_empty() {
+                break;
+            }
+        }
+        Ok(mismatches)
+    }
+
+    /// Same as `verify_auto_deductions`, but only checks the segment of the given builtin.
+    pub fn verify_auto_deductions_in_segment(
+        &self,
+        builtin: BuiltinName,
+    ) -> Result<(), VirtualMachineError> {
+        let builtin_runner = self
+            .builtin_runners
+            .iter()
+            .find(|b| b.name() == builtin)
+            .ok_or(VirtualMachineError::NoBuiltin(builtin))?;
+        let mut mismatches = Vec::new();
+        self.builtin_auto_deduction_mismatches(builtin_runner, false, &mut mismatches)?;
+        match mismatches.pop() {
+            Some(mismatch) => Err(mismatch.into()),
+            None => Ok(()),
+        }
+    }
+
+    fn builtin_auto_deduction_mismatches(
+        &self,
+        builtin: &BuiltinRunner,
+        collect_all_deduction_mismatches: bool,
+        mismatches: &mut Vec<AutoDeductionMismatch>,
+    ) -> Result<(), VirtualMachineError> {
+        let index: usize = builtin.base();
+        for (offset, value) in self.segments.memory.data[index].iter().enumerate() {
+            let address = Relocatable::from((index as isize, offset));
+            if let Some(deduced_memory_cell) = builtin
+                .deduce_memory_cell(address, &self.segments.memory)
+                .map_err(VirtualMachineError::RunnerError)?
+            {
+                let value = value.get_value();
+                if Some(&deduced_memory_cell) != value.as_ref() && value.is_some() {
+                    mismatches.push(AutoDeductionMismatch {
+                        builtin: builtin.name(),
+                        address,
+                        expected: deduced_memory_cell,
+                        found: value,
+                    });
+                    if !collect_all_deduction_mismatches {
+                        return Ok(());
                     }
                 }
             }
@@ -848,11 +974,93 @@ impl VirtualMachine {
         self.segments.memory.get(key).map(|x| x.into_owned())
     }
 
+    /// Captures the current execution state (registers, memory, segment sizes and builtin runners)
+    /// so that it can be restored later on with [`VirtualMachine::restore`].
+    /// This deep-clones the whole memory, so it is O(memory) and best kept out of hot loops.
+    pub fn snapshot(&self) -> VmSnapshot {
+        let memory = &self.segments.memory;
+        VmSnapshot {
+            pc: self.run_context.pc,
+            ap: self.run_context.ap,
+            fp: self.run_context.fp,
+            current_step: self.current_step,
+            trace_len: self.trace.as_ref().map(|trace| trace.len()),
+            rc_limits: self.rc_limits,
+            skip_instruction_execution: self.skip_instruction_execution,
+            run_finished: self.run_finished,
+            data: memory.data.clone(),
+            temp_data: memory.temp_data.clone(),
+            relocation_rules: memory.relocation_rules.clone(),
+            validated_addresses: memory.validated_addresses.clone(),
+            segment_sizes: self.segments.segment_sizes.clone(),
+            segment_used_sizes: self.segments.segment_used_sizes.clone(),
+            public_memory_offsets: self.segments.public_memory_offsets.clone(),
+            zero_segment_index: self.segments.zero_segment_index,
+            zero_segment_size: self.segments.zero_segment_size,
+            builtin_runners: self.builtin_runners.clone(),
+        }
+    }
+
+    /// Restores the execution state captured by [`VirtualMachine::snapshot`].
+    /// Memory validation rules are kept as is, as they are only added during initialization.
+    pub fn restore(&mut self, snapshot: VmSnapshot) {
+        self.run_context.pc = snapshot.pc;
+        self.run_context.ap = snapshot.ap;
+        self.run_context.fp = snapshot.fp;
+        self.current_step = snapshot.current_step;
+        if let (Some(trace), Some(trace_len)) = (self.trace.as_mut(), snapshot.trace_len) {
+            trace.truncate(trace_len);
+        }
+        self.rc_limits = snapshot.rc_limits;
+        self.skip_instruction_execution = snapshot.skip_instruction_execution;
+        self.run_finished = snapshot.run_finished;
+        let memory = &mut self.segments.memory;
+        memory.data = snapshot.data;
+        memory.temp_data = snapshot.temp_data;
+        memory.relocation_rules = snapshot.relocation_rules;
+        memory.validated_addresses = snapshot.validated_addresses;
+        self.segments.segment_sizes = snapshot.segment_sizes;
+        self.segments.segment_used_sizes = snapshot.segment_used_sizes;
+        self.segments.public_memory_offsets = snapshot.public_memory_offsets;
+        self.segments.zero_segment_index = snapshot.zero_segment_index;
+        self.segments.zero_segment_size = snapshot.zero_segment_size;
+        self.builtin_runners = snapshot.builtin_runners;
+    }
+
     /// Returns a reference to the vector with all builtins present in the virtual machine
     pub fn get_builtin_runners(&self) -> &Vec<BuiltinRunner> {
         &self.builtin_runners
     }
 
+    /// Returns the builtins present in the virtual machine sorted by their canonical order
+    /// (the order used by the layouts), regardless of the order in which they were added
+    pub fn builtin_runners_ordered(&self) -> Vec<&BuiltinRunner> {
+        let mut builtin_runners: Vec<&BuiltinRunner> = self.builtin_runners.iter().collect();
+        builtin_runners.sort_by_key(|builtin| builtin.name().canonical_index());
+        builtin_runners
+    }
+
+    /// Returns the number of instances used so far by each builtin, in the order they were added.
+    /// Can be called mid-run: if the segment sizes haven't been computed yet, the current length
+    /// of each segment is used instead, without modifying the segment manager.
+    /// Fails if the usage of any of the builtins can't be computed.
+    pub fn builtin_usage_snapshot(&self) -> Result<Vec<(BuiltinName, usize)>, MemoryError> {
+        let current_sizes;
+        let segments = if self.segments.segment_used_sizes.is_some() {
+            &self.segments
+        } else {
+            let mut segments = MemorySegmentManager::new();
+            segments.segment_used_sizes =
+                Some(self.segments.memory.data.iter().map(Vec::len).collect());
+            current_sizes = segments;
+            &current_sizes
+        };
+        self.builtin_runners
+            .iter()
+            .map(|builtin| Ok((builtin.name(), builtin.get_used_instances(segments)?)))
+            .collect()
+    }
+
     /// Returns a mutable reference to the vector with all builtins present in the virtual machine
     pub fn get_builtin_runners_as_mut(&mut self) -> &mut Vec<BuiltinRunner> {
         &mut self.builtin_runners
@@ -926,6 +1134,18 @@ impl VirtualMachine {
         self.segments.memory.get_integer_range(addr, size)
     }
 
+    ///Gets n relocatable values from memory starting from addr (n being size),
+    ///failing on the first cell that is either missing or not a relocatable.
+    pub fn get_relocatable_range(
+        &self,
+        addr: Relocatable,
+        size: usize,
+    ) -> Result<Vec<Relocatable>, VirtualMachineError> {
+        (0..size)
+            .map(|i| Ok(self.get_relocatable((addr + i)?)?))
+            .collect()
+    }
+
     pub fn get_range_check_builtin(
         &self,
     ) -> Result<&RangeCheckBuiltinRunner<RC_N_PARTS_STANDARD>, VirtualMachineError> {
@@ -1022,28 +1242,14 @@ impl VirtualMachine {
         &mut self,
         writer: &mut impl core::fmt::Write,
     ) -> Result<(), VirtualMachineError> {
-        let builtin = match self
-            .builtin_runners
-            .iter()
-            .find(|b| b.name() == BuiltinName::output)
-        {
-            Some(x) => x,
-            _ => return Ok(()),
+        let Some((output_base, size)) = self.get_output_segment() else {
+            return Ok(());
         };
-
-        let segment_used_sizes = self.segments.compute_effective_sizes();
-        let segment_index = builtin.base();
-        for i in 0..segment_used_sizes[segment_index] {
-            let formatted_value = match self
-                .segments
-                .memory
-                .get(&Relocatable::from((segment_index as isize, i)))
-            {
-                Some(val) => match val.as_ref() {
-                    MaybeRelocatable::Int(num) => format!("{}", signed_felt(*num)),
-                    MaybeRelocatable::RelocatableValue(rel) => format!("{}", rel),
-                },
-                _ => "<missing>".to_string(),
+        for value in self.get_range(output_base, size) {
+            let formatted_value = match value.as_deref() {
+                Some(MaybeRelocatable::Int(num)) => format!("{}", signed_felt(*num)),
+                Some(MaybeRelocatable::RelocatableValue(rel)) => format!("{}", rel),
+                None => "<missing>".to_string(),
             };
             writeln!(writer, "{formatted_value}")
                 .map_err(|_| VirtualMachineError::FailedToWriteOutput)?;
@@ -1052,6 +1258,35 @@ impl VirtualMachine {
         Ok(())
     }
 
+    /// Returns the values written to the output builtin's segment, in order.
+    /// Fails if there is no output builtin or if a cell is missing or holds a relocatable value.
+    pub fn get_output_values(&self) -> Result<Vec<Felt252>, RunnerError> {
+        let (output_base, size) = self
+            .get_output_segment()
+            .ok_or(RunnerError::MissingBuiltin(BuiltinName::output))?;
+        Ok(self
+            .get_integer_range(output_base, size)?
+            .into_iter()
+            .map(Cow::into_owned)
+            .collect())
+    }
+
+    /// Returns the base and size of the output builtin's segment, if the output builtin is
+    /// present. The current length of the segment is used if its size wasn't computed yet.
+    fn get_output_segment(&self) -> Option<(Relocatable, usize)> {
+        let segment_index = self
+            .builtin_runners
+            .iter()
+            .find(|b| b.name() == BuiltinName::output)?
+            .base();
+        let size = self
+            .segments
+            .get_segment_used_size(segment_index)
+            .or_else(|| self.segments.memory.data.get(segment_index).map(Vec::len))
+            .unwrap_or_default();
+        Some((Relocatable::from((segment_index as isize, 0)), size))
+    }
+
     /// Returns a list of addresses of memory cells that constitute the public memory.
     pub fn get_public_memory_addresses(&self) -> Result<Vec<(usize, usize)>, VirtualMachineError> {
         if let Some(relocation_table) = &self.relocation_table {
@@ -1250,6 +1485,8 @@ impl VirtualMachineBuilder {
             #[cfg(feature = "test_utils")]
             hooks: self.hooks,
             relocation_table: None,
+            #[cfg(feature = "profile_builtins")]
+            builtin_timings: None,
         }
     }
 }
@@ -3638,6 +3875,111 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "profile_builtins")]
+    fn builtin_timings_ec_op() {
+        let mut builtin = EcOpBuiltinRunner::new(Some(256), true);
+        builtin.base = 3;
+        let mut vm = vm!();
+        vm.builtin_runners.push(builtin.into());
+        vm.segments = segments![
+            (
+                (3, 0),
+                (
+                    "2962412995502985605007699495352191122971573493113767820301112397466445942584",
+                    10
+                )
+            ),
+            (
+                (3, 1),
+                (
+                    "214950771763870898744428659242275426967582168179217139798831865603966154129",
+                    10
+                )
+            ),
+            (
+                (3, 2),
+                (
+                    "874739451078007766457464989774322083649278607533249481151382481072868806602",
+                    10
+                )
+            ),
+            (
+                (3, 3),
+                (
+                    "152666792071518830868575557812948353041420400780739481342941381225525861407",
+                    10
+                )
+            ),
+            ((3, 4), 34)
+        ];
+        assert!(vm.builtin_timings().is_empty());
+
+        vm.enable_builtin_profiling();
+        assert_matches!(
+            vm.deduce_memory_cell(Relocatable::from((3, 5))),
+            Ok(Some(_))
+        );
+        let timings = vm.builtin_timings();
+        assert_eq!(timings.len(), 1);
+        assert!(timings.contains_key(&BuiltinName::ec_op));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn verify_auto_deductions_in_segment_ec_op() {
+        let mut builtin = EcOpBuiltinRunner::new(Some(256), true);
+        builtin.base = 3;
+        let mut vm = vm!();
+        vm.builtin_runners.push(builtin.into());
+        vm.segments = segments![
+            (
+                (3, 0),
+                (
+                    "2962412995502985605007699495352191122971573493113767820301112397466445942584",
+                    10
+                )
+            ),
+            (
+                (3, 1),
+                (
+                    "214950771763870898744428659242275426967582168179217139798831865603966154129",
+                    10
+                )
+            ),
+            (
+                (3, 2),
+                (
+                    "874739451078007766457464989774322083649278607533249481151382481072868806602",
+                    10
+                )
+            ),
+            (
+                (3, 3),
+                (
+                    "152666792071518830868575557812948353041420400780739481342941381225525861407",
+                    10
+                )
+            ),
+            ((3, 4), 34),
+            (
+                (3, 5),
+                (
+                    "2778063437308421278851140253538604815869848682781135193774472480292420096757",
+                    10
+                )
+            )
+        ];
+        assert_matches!(
+            vm.verify_auto_deductions_in_segment(BuiltinName::ec_op),
+            Ok(())
+        );
+        assert_matches!(
+            vm.verify_auto_deductions_in_segment(BuiltinName::bitwise),
+            Err(VirtualMachineError::NoBuiltin(BuiltinName::bitwise))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     /* Program used:
@@ -3661,6 +4003,49 @@ mod tests {
         assert_matches!(vm.verify_auto_deductions(), Ok(()));
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn collect_auto_deduction_mismatches_bitwise() {
+        let mut builtin = BitwiseBuiltinRunner::new(Some(256), true);
+        builtin.base = 2;
+        let mut vm = vm!();
+        vm.builtin_runners.push(builtin.into());
+        vm.segments = segments![
+            ((2, 0), 12),
+            ((2, 1), 10),
+            ((2, 2), 7),
+            ((2, 3), 5),
+            ((2, 4), 14)
+        ];
+
+        assert_eq!(
+            vm.collect_auto_deduction_mismatches(true).unwrap(),
+            vec![
+                AutoDeductionMismatch {
+                    builtin: BuiltinName::bitwise,
+                    address: relocatable!(2, 2),
+                    expected: MaybeRelocatable::from(8),
+                    found: Some(MaybeRelocatable::from(7)),
+                },
+                AutoDeductionMismatch {
+                    builtin: BuiltinName::bitwise,
+                    address: relocatable!(2, 3),
+                    expected: MaybeRelocatable::from(6),
+                    found: Some(MaybeRelocatable::from(5)),
+                },
+            ]
+        );
+        assert_eq!(
+            vm.collect_auto_deduction_mismatches(false).unwrap().len(),
+            1
+        );
+        assert_matches!(
+            vm.verify_auto_deductions(),
+            Err(VirtualMachineError::InconsistentAutoDeduction(bx))
+            if *bx == (BuiltinName::bitwise, MaybeRelocatable::from(8), Some(MaybeRelocatable::from(7)))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     /* Program used:
@@ -3876,6 +4261,76 @@ mod tests {
         assert_eq!(builtins[1].name(), BuiltinName::bitwise);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_builtin_runners_ordered() {
+        let mut vm = vm!();
+        vm.builtin_runners
+            .push(BitwiseBuiltinRunner::new(Some(256), true).into());
+        vm.builtin_runners
+            .push(OutputBuiltinRunner::new(true).into());
+        vm.builtin_runners
+            .push(HashBuiltinRunner::new(Some(8), true).into());
+
+        let names: Vec<BuiltinName> = vm
+            .builtin_runners_ordered()
+            .iter()
+            .map(|builtin| builtin.name())
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                BuiltinName::output,
+                BuiltinName::pedersen,
+                BuiltinName::bitwise
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn builtin_usage_snapshot_mid_run() {
+        let mut vm = vm!();
+        vm.builtin_runners
+            .push(OutputBuiltinRunner::new(true).into());
+        vm.builtin_runners
+            .push(BitwiseBuiltinRunner::new(Some(256), true).into());
+        // The builtin segments don't exist yet
+        assert_eq!(
+            vm.builtin_usage_snapshot(),
+            Err(MemoryError::MissingSegmentUsedSizes)
+        );
+        for builtin in vm.builtin_runners.iter_mut() {
+            builtin.initialize_segments(&mut vm.segments);
+        }
+        assert_eq!(
+            vm.builtin_usage_snapshot(),
+            Ok(vec![(BuiltinName::output, 0), (BuiltinName::bitwise, 0)])
+        );
+
+        let mut previous = vm.builtin_usage_snapshot().unwrap();
+        for i in 0..6 {
+            // Each iteration writes an output cell and the inputs of a bitwise instance
+            vm.insert_value((0, i).into(), Felt252::from(i)).unwrap();
+            vm.insert_value((1, 5 * i).into(), Felt252::from(i))
+                .unwrap();
+            vm.insert_value((1, 5 * i + 1).into(), Felt252::from(i))
+                .unwrap();
+            let usage = vm.builtin_usage_snapshot().unwrap();
+            for ((name, before), (_, after)) in previous.iter().zip(usage.iter()) {
+                assert!(before <= after, "{name} usage decreased");
+            }
+            previous = usage;
+        }
+        assert_eq!(
+            previous,
+            vec![(BuiltinName::output, 6), (BuiltinName::bitwise, 6)]
+        );
+        // Sampling must not finalize the segment sizes
+        assert_eq!(vm.segments.segment_used_sizes, None);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_get_output_builtin_mut() {
@@ -3900,6 +4355,35 @@ mod tests {
         assert_eq!(vm_output_builtin.included, output_builtin.included);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_output_values() {
+        let mut vm = vm!();
+        assert_eq!(
+            vm.get_output_values(),
+            Err(RunnerError::MissingBuiltin(BuiltinName::output))
+        );
+
+        let mut output_builtin = OutputBuiltinRunner::new(true);
+        output_builtin.initialize_segments(&mut vm.segments);
+        vm.builtin_runners.push(output_builtin.into());
+        assert_eq!(vm.get_output_values(), Ok(vec![]));
+
+        vm.segments = segments![((0, 0), 3), ((0, 1), 5)];
+        assert_eq!(
+            vm.get_output_values(),
+            Ok(vec![Felt252::from(3), Felt252::from(5)])
+        );
+
+        vm.segments = segments![((0, 0), 3), ((0, 1), (2, 0))];
+        assert_eq!(
+            vm.get_output_values(),
+            Err(RunnerError::Memory(MemoryError::ExpectedInteger(Box::new(
+                (0, 1).into()
+            ))))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_range_for_continuous_memory() {
@@ -3966,6 +4450,41 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_relocatable_range() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0)), ((1, 1), (2, 3)), ((1, 2), (3, 1))];
+
+        assert_eq!(
+            vm.get_relocatable_range(Relocatable::from((1, 0)), 3)
+                .unwrap(),
+            vec![
+                Relocatable::from((2, 0)),
+                Relocatable::from((2, 3)),
+                Relocatable::from((3, 1))
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_relocatable_range_errors() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0)), ((1, 2), (3, 1)), ((1, 3), 4)];
+
+        assert_matches!(
+            vm.get_relocatable_range(Relocatable::from((1, 0)), 3),
+            Err(VirtualMachineError::Memory(MemoryError::UnknownMemoryCell(addr)))
+                if *addr == Relocatable::from((1, 1))
+        );
+        assert_matches!(
+            vm.get_relocatable_range(Relocatable::from((1, 2)), 2),
+            Err(VirtualMachineError::Memory(MemoryError::ExpectedRelocatable(addr)))
+                if *addr == Relocatable::from((1, 3))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_segment_used_size_after_computing_used() {
@@ -29,9 +29,9 @@ pub struct MemorySegmentManager {
     pub public_memory_offsets: HashMap<usize, Vec<(usize, usize)>>,
     // Segment index of the zero segment index, a memory segment filled with zeroes, used exclusively by builtin runners
     // This segment will never have index 0 so we use 0 to represent uninitialized value
-    zero_segment_index: usize,
+    pub(crate) zero_segment_index: usize,
     // Segment size of the zero segment index
-    zero_segment_size: usize,
+    pub(crate) zero_segment_size: usize,
 }
 
 impl MemorySegmentManager {
@@ -238,6 +238,36 @@ impl MemorySegmentManager {
         Ok(memory_holes)
     }
 
+    /// Returns, for each segment, the amount of unwritten cells between its lowest and highest
+    /// written offsets. Unlike `get_memory_holes`, it relies on the written cells instead of the
+    /// accessed ones, and doesn't require the segment sizes to be computed.
+    pub fn get_memory_holes_by_segment(&self) -> Vec<usize> {
+        self.memory
+            .data
+            .iter()
+            .map(|segment| {
+                match (
+                    segment.iter().position(|cell| cell.is_some()),
+                    segment.iter().rposition(|cell| cell.is_some()),
+                ) {
+                    (Some(first), Some(last)) => segment[first..=last]
+                        .iter()
+                        .filter(|cell| cell.is_none())
+                        .count(),
+                    _ => 0,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the total amount of unwritten cells between the lowest and highest written offsets
+    /// of each segment, i.e. the sum of [MemorySegmentManager::get_memory_holes_by_segment].
+    /// Unlike `get_memory_holes`, it doesn't skip the builtin segments and doesn't require the
+    /// segment sizes to be computed.
+    pub fn get_written_memory_holes(&self) -> usize {
+        self.get_memory_holes_by_segment().into_iter().sum()
+    }
+
     /// Returns a list of addresses of memory cells that constitute the public memory.
     /// segment_offsets is the result of self.relocate_segments()
     pub fn get_public_memory_addresses(
@@ -318,9 +348,20 @@ impl MemorySegmentManager {
         for _ in 0..n_extra_segments {
             self.add();
         }
-        // Load previous execution memory
+        // Load previous execution memory, rejecting addresses outside of the run's segments
+        let n_segments = self.num_segments() as isize;
         for ((si, so), val) in pie_memory.0.iter() {
-            self.memory.insert((*si as isize, *so).into(), val)?;
+            let addr = Relocatable::from((*si as isize, *so));
+            let out_of_range = match val {
+                MaybeRelocatable::RelocatableValue(value) => [addr, *value]
+                    .into_iter()
+                    .find(|addr| addr.segment_index < 0 || addr.segment_index >= n_segments),
+                MaybeRelocatable::Int(_) => (addr.segment_index >= n_segments).then_some(addr),
+            };
+            if let Some(addr) = out_of_range {
+                return Err(MemoryError::PieSegmentOutOfRange { addr });
+            }
+            self.memory.insert(addr, val)?;
         }
         Ok(())
     }
@@ -364,6 +405,43 @@ mod tests {
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn load_pie_memory_segment_out_of_range() {
+        let mut segments = MemorySegmentManager::new();
+        segments.add();
+        assert_eq!(
+            segments.load_pie_memory(
+                &CairoPieMemory(vec![((0, 0), 1.into()), ((1, 0), (0, 0).into())]),
+                1
+            ),
+            Ok(())
+        );
+
+        // Address in a segment beyond the extra segments
+        let mut segments = MemorySegmentManager::new();
+        segments.add();
+        assert_eq!(
+            segments.load_pie_memory(
+                &CairoPieMemory(vec![((0, 0), 1.into()), ((2, 0), 2.into())]),
+                1
+            ),
+            Err(MemoryError::PieSegmentOutOfRange {
+                addr: relocatable!(2, 0)
+            })
+        );
+
+        // Value pointing to a nonexistent segment
+        let mut segments = MemorySegmentManager::new();
+        segments.add();
+        assert_eq!(
+            segments.load_pie_memory(&CairoPieMemory(vec![((0, 0), (5, 3).into())]), 0),
+            Err(MemoryError::PieSegmentOutOfRange {
+                addr: relocatable!(5, 3)
+            })
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn add_segment_no_size() {
@@ -787,6 +865,28 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_memory_holes_by_segment() {
+        let mut memory_segment_manager = MemorySegmentManager::new();
+        memory_segment_manager.memory = memory![
+            ((0, 0), 1),
+            ((0, 3), 2),
+            ((0, 4), 3),
+            ((1, 2), 4),
+            ((1, 4), 5),
+            ((1, 8), 6),
+            ((2, 0), 7)
+        ];
+        memory_segment_manager.add();
+
+        assert_eq!(
+            memory_segment_manager.get_memory_holes_by_segment(),
+            vec![2, 4, 0, 0]
+        );
+        assert_eq!(memory_segment_manager.get_written_memory_holes(), 6);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_memory_size_missing_segment() {